@@ -0,0 +1,127 @@
+use crate::{check_hard_mode, Dictionary, Error, GameResult, Guess, Guesser, Pattern, Turn, Word, MAX_GUESSES};
+use std::{borrow::Cow, time::Instant};
+
+/// A host that never picks an answer. It keeps every answer consistent with the game so far, and
+/// after each guess gives the pattern that leaves as many of them as it can, so the guesser only
+/// wins once it has cornered the host.
+pub struct Absurdle<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    answers: Vec<Word<N>>,
+    hard_mode: bool,
+    max_guesses: usize,
+}
+
+impl Absurdle<'static> {
+    /// Accepts any word in the embedded `dictionary.txt`, and picks from `answers.txt`.
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded(), Dictionary::embedded_answers())
+    }
+}
+
+impl<'d, const N: usize> Absurdle<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>, answers: &Dictionary<N>) -> Self {
+        Absurdle {
+            dictionary,
+            answers: answers.words().iter().map(|&(answer, _)| answer).collect(),
+            hard_mode: false,
+            max_guesses: MAX_GUESSES,
+        }
+    }
+
+    /// In hard mode, every guess must keep revealed greens in place and reuse revealed yellows.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// How many guesses the guesser gets to corner the host.
+    pub fn with_max_guesses(mut self, max_guesses: usize) -> Self {
+        self.max_guesses = max_guesses;
+        self
+    }
+
+    /// Like `try_play`, but panics on an invalid guess.
+    pub fn play<G: Guesser<N>>(&self, guesser: G) -> GameResult<N> {
+        self.try_play(guesser).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Plays until the guesser guesses the only answer left. `answer` of the result is that
+    /// answer, or when the guesser runs out of guesses, the first of those still left.
+    pub fn try_play<G: Guesser<N>>(&self, mut guesser: G) -> Result<GameResult<N>, Error> {
+        let mut pool = self.answers.clone();
+        let mut history = Vec::new();
+        let mut turns = Vec::new();
+        while turns.len() < self.max_guesses && !pool.is_empty() {
+            let start = Instant::now();
+            let guess = guesser.try_guess(&history)?;
+            let elapsed = start.elapsed();
+            if !self.dictionary.contains(&guess) && !pool.contains(&guess) {
+                return Err(Error::InvalidGuess(String::from_utf8_lossy(&guess).into_owned()));
+            }
+            if self.hard_mode {
+                check_hard_mode(&history, &guess)?;
+            }
+            let pattern = self.respond(&mut pool, &guess);
+            turns.push(Turn {
+                word: guess,
+                mask: pattern.mask(),
+                remaining: guesser.remaining(),
+                elapsed,
+            });
+            if pattern.is_solved() {
+                return Ok(GameResult { answer: guess, turns, won: true });
+            }
+            history.push(Guess { word: Cow::Owned(guess), mask: pattern.mask() });
+        }
+        let answer = pool.first().copied().unwrap_or([b'?'; N]);
+        Ok(GameResult { answer, turns, won: false })
+    }
+
+    /// Picks the pattern that keeps the most of `pool`, and keeps just those. Ties go to the
+    /// pattern that gives away less, i.e. has more grays.
+    fn respond(&self, pool: &mut Vec<Word<N>>, guess: &Word<N>) -> Pattern<N> {
+        let mut in_pattern = vec![0usize; Pattern::<N>::COUNT];
+        for answer in pool.iter() {
+            in_pattern[Pattern::compute(answer, guess).index()] += 1;
+        }
+        let pattern = Pattern::all()
+            .max_by_key(|pattern| (in_pattern[pattern.index()], pattern.id()))
+            .expect("there is at least one pattern");
+        pool.retain(|answer| Pattern::compute(answer, guess) == pattern);
+        pattern
+    }
+}
+
+impl Default for Absurdle<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Absurdle;
+    use crate::{algorithms::Matrix, Dictionary};
+
+    #[test]
+    fn cornered() {
+        let d = Dictionary::<5>::parse("catch\nhatch\nlatch\nmatch\nwatch\nbatch\n").unwrap();
+        let answers = Dictionary::parse("catch\nhatch\nlatch\nmatch\n").unwrap();
+        let host = Absurdle::with_dictionary(&d, &answers);
+        // every guess only ever rules out itself, so the host holds out until the last one
+        let result = host.play(Matrix::with_dictionary(&answers));
+        assert!(result.won);
+        assert_eq!(result.guesses(), Some(4));
+
+        let result = host.with_max_guesses(3).play(Matrix::with_dictionary(&answers));
+        assert!(!result.won);
+        assert_eq!(result.turns.len(), 3);
+    }
+
+    #[test]
+    fn full_game() {
+        let result = Absurdle::new().with_max_guesses(12).play(Matrix::new());
+        assert!(result.won);
+        assert!(result.turns.windows(2).all(|w| w[0].remaining > w[1].remaining));
+    }
+}
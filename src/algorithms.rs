@@ -1,3 +1,119 @@
+use crate::{Dictionary, Explanation, Guess, Pattern, Row, Word};
+
+/// Whether the precomputed openers hold: they were searched for on the built-in dictionary with
+/// words weighed by their counts, and any other dictionary or prior needs a search of its own.
+fn precomputed<const N: usize>(dictionary: &Dictionary<N>, weights: &[f64]) -> bool {
+    dictionary.is_embedded()
+        && dictionary.words().iter().zip(weights).all(|(&(_, count), &weight)| weight == count as f64)
+}
+
+/// The precomputed best opener, if it holds for `dictionary` weighed by `weights`.
+fn opener<const N: usize>(dictionary: &Dictionary<N>, weights: &[f64]) -> Option<Word<N>> {
+    if !precomputed(dictionary, weights) {
+        return None;
+    }
+    b"tares".as_slice().try_into().ok()
+}
+
+/// The opener with the smallest worst case, for `Minimax`, if it holds like `opener`.
+fn minimax_opener<const N: usize>(dictionary: &Dictionary<N>, weights: &[f64]) -> Option<Word<N>> {
+    if !precomputed(dictionary, weights) {
+        return None;
+    }
+    b"serai".as_slice().try_into().ok()
+}
+
+/// Drops the dictionary indices from `remaining` that wouldn't have given `guess` its mask.
+pub(crate) fn retain_consistent<const N: usize>(dictionary: &Dictionary<N>, remaining: &mut Vec<usize>, guess: &Guess<N>) {
+    match dictionary.index_of(&guess.word) {
+        Some(index) => {
+            let row = dictionary.row(index);
+            let pattern = guess.pattern();
+            remaining.retain(|&answer| row.get(answer) == pattern);
+        }
+        // guesses from outside the dictionary have no row to look up
+        None => remaining.retain(|&answer| guess.matches(&dictionary.words()[answer].0)),
+    }
+}
+
+/// Entropy of the patterns a guess with this `row` gets against `remaining`, each answer counting
+/// with its weight. `total` is the weight of all of `remaining`, and `in_pattern_total` is
+/// scratch space with room for every pattern.
+pub(crate) fn entropy<const N: usize>(
+    row: Row<'_, N>,
+    remaining: &[usize],
+    weights: &[f64],
+    total: f64,
+    in_pattern_total: &mut [f64],
+) -> f64 {
+    in_pattern_total.fill(0.0);
+    for &answer in remaining {
+        in_pattern_total[row.get(answer).index()] += weights[answer];
+    }
+    // - SUM_i p_i * log(p_i)
+    in_pattern_total
+        .iter()
+        .filter(|&&in_pattern| in_pattern != 0.0)
+        .map(|&in_pattern| {
+            let p_of_this_pattern = in_pattern / total;
+            -(p_of_this_pattern * p_of_this_pattern.log2())
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    word: usize,
+    goodness: f64,
+    /// Whether `word` is still one of the remaining words, so it could win outright.
+    possible: bool,
+}
+
+/// The word of `candidates` with the most entropy over `remaining`, which has to be sorted.
+/// Scores that only differ by rounding are a tie, which goes to a word that could be the answer.
+fn most_entropy<const N: usize>(
+    dictionary: &Dictionary<N>,
+    weights: &[f64],
+    remaining: &[usize],
+    candidates: impl Iterator<Item = usize>,
+) -> Option<usize> {
+    let total: f64 = remaining.iter().map(|&answer| weights[answer]).sum();
+    let mut in_pattern_total = vec![0.0; Pattern::<N>::COUNT];
+    let mut best: Option<Candidate> = None;
+    for word in candidates {
+        let goodness = entropy(dictionary.row(word), remaining, weights, total, &mut in_pattern_total);
+        let possible = remaining.binary_search(&word).is_ok();
+        let better = match best {
+            None => true,
+            Some(c) if possible != c.possible && (goodness - c.goodness).abs() < 1e-9 => possible,
+            Some(c) => goodness > c.goodness,
+        };
+        if better {
+            best = Some(Candidate { word, goodness, possible });
+        }
+    }
+    best.map(|c| c.word)
+}
+
+/// `Guesser::explain` for the guessers that keep `remaining` and `weights` by dictionary index,
+/// having just picked `chosen`. An opener isn't `searched` for, so it's the only candidate
+/// listed; otherwise all of `pool` is ranked.
+fn explanation<const N: usize>(
+    dictionary: &Dictionary<N>,
+    weights: &[f64],
+    remaining: &[usize],
+    pool: impl Iterator<Item = usize>,
+    chosen: Word<N>,
+    searched: bool,
+    top: usize,
+) -> Option<Explanation<N>> {
+    let chosen = dictionary.index_of(&chosen)?;
+    if !searched {
+        return Some(Explanation::rank(dictionary, weights, remaining, std::iter::once(chosen), chosen, top));
+    }
+    Some(Explanation::rank(dictionary, weights, remaining, pool, chosen, top))
+}
+
 mod naive;
 pub use naive::Naive;
 
@@ -8,4 +124,19 @@ mod vecremain;
 pub use vecremain::VecRem;
 
 mod once;
-pub use once::Once;
\ No newline at end of file
+pub use once::Once;
+
+mod matrix;
+pub use matrix::Matrix;
+
+mod lookahead;
+pub use lookahead::Lookahead;
+
+mod minimax;
+pub use minimax::Minimax;
+
+mod constrained;
+pub use constrained::Constrained;
+
+mod multientropy;
+pub use multientropy::MultiEntropy;
@@ -1,50 +1,70 @@
 use std::{collections::HashMap, borrow::Cow};
 
-use crate::{Guesser, Guess, DICT, Correctness, Word};
+use crate::{prior::Counts, Dictionary, Error, Prior, Explanation, Guesser, Guess, Correctness, Word};
 
-pub struct Allocs{
-    remaining: HashMap<&'static Word, usize>,
+pub struct Allocs<'d, const N: usize = 5> {
+    /// The precomputed best opener, when it holds for this dictionary and prior.
+    opener: Option<Word<N>>,
+    remaining: HashMap<&'d Word<N>, f64>,
 }
 
-impl Allocs {
+#[allow(clippy::new_without_default)]
+impl Allocs<'static> {
     pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Allocs<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &dyn Prior<N>) -> Self {
+        let weights = prior.weights(dictionary);
         Allocs {
-            remaining: HashMap::from_iter(
-                DICT.lines().map(
-                    |line| {
-                    let (word, count) = line.split_once(" ").expect("every line is word + space + occurance");
-                    let count:usize = count.parse().expect("every count is a number");
-                    (word.as_bytes().try_into().expect("5 letter words"), count)
-                    }
-            ))       
+            opener: super::opener(dictionary, &weights),
+            remaining: HashMap::from_iter(dictionary.words().iter().map(|(word, _)| word).zip(weights)),
         }
     }
 }
+
 #[derive(Debug, Clone, Copy)]
-struct Candidate {
-    word: &'static Word,
+struct Candidate<'d, const N: usize> {
+    word: &'d Word<N>,
     goodness: f64,
 }
 
-impl Guesser for Allocs {
-    fn guess(&mut self, history: &[Guess]) -> Word{
-        if history.is_empty(){
-            return *b"tares";
+impl<const N: usize> Allocs<'_, N> {
+    /// Narrows `remaining` by the newest guess and picks the word with the most entropy over it,
+    /// adding every word it weighs to `scored`.
+    fn choose(&mut self, history: &[Guess<N>], mut scored: Option<&mut Vec<(Word<N>, f64)>>) -> Word<N> {
+        // precomputed best opener for the built-in dictionary
+        if history.is_empty() {
+            if let Some(tares) = self.opener {
+                return tares;
+            }
         }
         if let Some(last) = history.last(){
             // update self.remaining based on history
             self.remaining.retain(|word, _| last.matches(word));
         }
-        let remaining_count: usize = self.remaining.iter().map(|(_, &count)|{count}).sum();
-        let mut best:Option<Candidate>= None;
+        let remaining_count: f64 = self.remaining.iter().map(|(_, &count)|{count}).sum();
+        let mut best:Option<Candidate<N>>= None;
 
+        #[allow(clippy::for_kv_map)]
         for (&word, _) in &self.remaining {
             // - SUM_i p_i * log(p_i)
             let mut goodness = 0.0;
             for pattern in Correctness::patterns(){
                 // considering a world where we did guess word and got pattern as the correctness.
                 // Now compute what then is left
-                let mut in_pattern_total = 0;
+                let mut in_pattern_total = 0.0;
                 for (candidate, count) in &self.remaining {
                     let g = Guess {
                         word:Cow::Borrowed(word),
@@ -54,10 +74,13 @@ impl Guesser for Allocs {
                         in_pattern_total += count;
                     } 
                 }
-                if in_pattern_total == 0 {continue;}
-                let p_of_this_pattern = in_pattern_total as f64 / remaining_count as f64;
+                if in_pattern_total == 0.0 {continue;}
+                let p_of_this_pattern = in_pattern_total / remaining_count;
                 goodness += -(p_of_this_pattern * p_of_this_pattern.log2());
             }
+            if let Some(scored) = &mut scored {
+                scored.push((*word, goodness));
+            }
             if let Some(c) = best {
                 // is this one better?
                 if goodness > c.goodness {
@@ -73,3 +96,19 @@ impl Guesser for Allocs {
     }
 }
 
+impl<const N: usize> Guesser<N> for Allocs<'_, N> {
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn explain(&mut self, history: &[Guess<N>], top: usize) -> Option<Explanation<N>> {
+        let mut scored = Vec::new();
+        let chosen = self.choose(history, Some(&mut scored));
+        let remaining = self.remaining.iter().map(|(&word, &weight)| (word, weight));
+        Some(Explanation::from_scored(chosen, remaining, scored, top))
+    }
+
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.choose(history, None)
+    }
+}
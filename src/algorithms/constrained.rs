@@ -0,0 +1,76 @@
+use crate::{prior::Counts, Constraints, Dictionary, Error, Explanation, Guess, Guesser, Prior, Word};
+
+/// Like `Matrix`, but prunes the remaining words with `Constraints` built from the whole
+/// history rather than recomputing a mask for the last guess. That also means it doesn't need
+/// to be shown every turn of a game.
+pub struct Constrained<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    /// Dictionary indices of the words still consistent with the history.
+    remaining: Vec<usize>,
+    /// How likely each dictionary word is, by index.
+    weights: Vec<f64>,
+}
+
+impl Constrained<'static> {
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Constrained<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &dyn Prior<N>) -> Self {
+        Constrained {
+            dictionary,
+            remaining: (0..dictionary.len()).collect(),
+            weights: prior.weights(dictionary),
+        }
+    }
+}
+
+impl Default for Constrained<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Guesser<N> for Constrained<'_, N> {
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn explain(&mut self, history: &[Guess<N>], top: usize) -> Option<Explanation<N>> {
+        let chosen = self.try_guess(history).ok()?;
+        let pool = self.remaining.iter().copied();
+        let searched = !history.is_empty() || super::opener(self.dictionary, &self.weights) != Some(chosen);
+        super::explanation(self.dictionary, &self.weights, &self.remaining, pool, chosen, searched, top)
+    }
+
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails once no dictionary word is left, which happens when the answer isn't one of them.
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, Error> {
+        // precomputed best opener for the built-in dictionary
+        if history.is_empty() {
+            if let Some(tares) = super::opener(self.dictionary, &self.weights) {
+                return Ok(tares);
+            }
+        }
+        let words = self.dictionary.words();
+        let constraints = Constraints::from_history(history);
+        self.remaining.retain(|&answer| constraints.matches(&words[answer].0));
+
+        let best = super::most_entropy(self.dictionary, &self.weights, &self.remaining, self.remaining.iter().copied());
+        best.map(|word| words[word].0).ok_or(Error::NoCandidates)
+    }
+}
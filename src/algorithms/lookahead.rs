@@ -0,0 +1,175 @@
+use crate::{prior::Counts, Dictionary, Error, Explanation, Guess, Guesser, Pattern, Prior, Word, MAX_GUESSES};
+
+/// Picks the guess with the fewest expected guesses left, looking two moves ahead.
+///
+/// Every candidate splits the remaining words into buckets by pattern, and each bucket is scored
+/// by the best follow-up guess for it. Only the `beam` candidates with the most entropy are
+/// looked at on either move, so the search stays cheap even on the full dictionary. When every
+/// one of them could run out of guesses, it plays the word with the most entropy like `Matrix`.
+pub struct Lookahead<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    /// Dictionary indices of the words still consistent with the history.
+    remaining: Vec<usize>,
+    /// How likely each dictionary word is, by index.
+    weights: Vec<f64>,
+    beam: usize,
+}
+
+impl Lookahead<'static> {
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Lookahead<'d, N> {
+    pub const DEFAULT_BEAM: usize = 8;
+
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &dyn Prior<N>) -> Self {
+        Lookahead {
+            dictionary,
+            remaining: (0..dictionary.len()).collect(),
+            weights: prior.weights(dictionary),
+            beam: Self::DEFAULT_BEAM,
+        }
+    }
+
+    /// How many candidates to look ahead from on each move; at least 1.
+    pub fn with_beam(mut self, beam: usize) -> Self {
+        self.beam = beam.max(1);
+        self
+    }
+
+    fn weight(&self, pool: &[usize]) -> f64 {
+        pool.iter().map(|&word| self.weights[word]).sum()
+    }
+
+    /// The `beam` words of `pool` that split it best, best first.
+    fn shortlist(&self, pool: &[usize]) -> Vec<usize> {
+        let total = self.weight(pool);
+        let mut in_pattern_total = vec![0.0; Pattern::<N>::COUNT];
+        let mut scored: Vec<(usize, f64)> = pool
+            .iter()
+            .map(|&word| {
+                let row = self.dictionary.row(word);
+                (word, super::entropy(row, pool, &self.weights, total, &mut in_pattern_total))
+            })
+            .collect();
+        // stable, so ties keep dictionary order
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(self.beam);
+        scored.into_iter().map(|(word, _)| word).collect()
+    }
+
+    /// Expected number of guesses to solve `pool`, counting `guess` itself, when the buckets it
+    /// leaves are searched `depth` more moves before falling back to `estimate`. Also the most
+    /// guesses that plan could take, assuming a bucket it stops searching takes one guess per word.
+    fn expected(&self, guess: usize, pool: &[usize], depth: usize) -> (f64, usize) {
+        let row = self.dictionary.row(guess);
+        let mut buckets = vec![Vec::new(); Pattern::<N>::COUNT];
+        for &answer in pool {
+            buckets[row.get(answer).index()].push(answer);
+        }
+        let total = self.weight(pool);
+        let mut worst = 1;
+        let expected = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| !bucket.is_empty())
+            .map(|(pattern, bucket)| {
+                let p = self.weight(bucket) / total;
+                if pattern == Pattern::<N>::ALL_CORRECT.index() {
+                    p
+                } else {
+                    let (expected, most) = self.follow_up(bucket, depth);
+                    worst = worst.max(1 + most);
+                    p * (1.0 + expected)
+                }
+            })
+            .sum();
+        (expected, worst)
+    }
+
+    /// Expected number of guesses to solve `pool` from scratch, and the most it could take.
+    fn follow_up(&self, pool: &[usize], depth: usize) -> (f64, usize) {
+        if pool.len() == 1 {
+            return (1.0, 1);
+        }
+        if depth == 0 {
+            return (estimate(pool.len()), pool.len());
+        }
+        self.shortlist(pool)
+            .into_iter()
+            .map(|guess| self.expected(guess, pool, depth - 1))
+            .fold((f64::INFINITY, usize::MAX), |best, next| if next.0 < best.0 { next } else { best })
+    }
+}
+
+/// Rough guesses left for `n` words once the lookahead stops: a coin flip for two, and about
+/// one more guess for every four bits of uncertainty beyond that.
+fn estimate(n: usize) -> f64 {
+    match n {
+        0 | 1 => 1.0,
+        n => 1.5 + (n as f64 / 2.0).log2() / 4.0,
+    }
+}
+
+impl Default for Lookahead<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Guesser<N> for Lookahead<'_, N> {
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn explain(&mut self, history: &[Guess<N>], top: usize) -> Option<Explanation<N>> {
+        let chosen = self.try_guess(history).ok()?;
+        let pool = self.remaining.iter().copied();
+        let searched = !history.is_empty() || super::opener(self.dictionary, &self.weights) != Some(chosen);
+        super::explanation(self.dictionary, &self.weights, &self.remaining, pool, chosen, searched, top)
+    }
+
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails once no dictionary word is left, which happens when the answer isn't one of them.
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, Error> {
+        // precomputed best opener for the built-in dictionary
+        if history.is_empty() {
+            if let Some(tares) = super::opener(self.dictionary, &self.weights) {
+                return Ok(tares);
+            }
+        }
+        let words = self.dictionary.words();
+        if let Some(last) = history.last() {
+            // update self.remaining based on history
+            super::retain_consistent(self.dictionary, &mut self.remaining, last);
+        }
+        let shortlist = self.shortlist(&self.remaining);
+        // the guess this one is making counts against the limit too
+        let left = MAX_GUESSES.saturating_sub(history.len());
+        let mut best: Option<(usize, f64)> = None;
+        for &word in &shortlist {
+            let (score, worst) = self.expected(word, &self.remaining, 1);
+            // a plan that can run out of guesses isn't worth its better average
+            if worst <= left && best.is_none_or(|(_, s)| score < s) {
+                best = Some((word, score));
+            }
+        }
+        // otherwise make the move Matrix would, which is the word with the most entropy
+        let best = best.map(|(word, _)| word).or(shortlist.first().copied());
+        best.map(|word| words[word].0).ok_or(Error::NoCandidates)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{prior::Counts, Dictionary, Error, Explanation, Guess, Guesser, Prior, Word};
+
+/// Like `Once`, but scores candidates against the dictionary's pattern table instead of
+/// recomputing masks, bucketing the remaining answers by pattern in a single pass.
+pub struct Matrix<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    /// Dictionary indices of the words still consistent with the history.
+    remaining: Vec<usize>,
+    /// How likely each dictionary word is, by index.
+    weights: Vec<f64>,
+    probes: bool,
+}
+
+impl Matrix<'static> {
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Matrix<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &dyn Prior<N>) -> Self {
+        Matrix {
+            dictionary,
+            remaining: (0..dictionary.len()).collect(),
+            weights: prior.weights(dictionary),
+            probes: false,
+        }
+    }
+
+    /// Also consider guesses that can't be the answer anymore, when they split the remaining
+    /// words better. Those guesses break the hard mode rules.
+    pub fn with_probes(mut self, probes: bool) -> Self {
+        self.probes = probes;
+        self
+    }
+}
+
+impl Default for Matrix<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Guesser<N> for Matrix<'_, N> {
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn explain(&mut self, history: &[Guess<N>], top: usize) -> Option<Explanation<N>> {
+        let chosen = self.try_guess(history).ok()?;
+        let pool: Box<dyn Iterator<Item = usize>> = if self.probes {
+            Box::new(0..self.dictionary.len())
+        } else {
+            Box::new(self.remaining.iter().copied())
+        };
+        let searched = !history.is_empty() || super::opener(self.dictionary, &self.weights) != Some(chosen);
+        super::explanation(self.dictionary, &self.weights, &self.remaining, pool, chosen, searched, top)
+    }
+
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails once no dictionary word is left, which happens when the answer isn't one of them.
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, Error> {
+        // precomputed best opener for the built-in dictionary
+        if history.is_empty() {
+            if let Some(tares) = super::opener(self.dictionary, &self.weights) {
+                return Ok(tares);
+            }
+        }
+        if let Some(last) = history.last() {
+            // update self.remaining based on history
+            super::retain_consistent(self.dictionary, &mut self.remaining, last);
+        }
+        if self.remaining.is_empty() {
+            // probes would still score, but there's no answer left to find
+            return Err(Error::NoCandidates);
+        }
+        let candidates: Box<dyn Iterator<Item = usize>> = if self.probes {
+            Box::new(0..self.dictionary.len())
+        } else {
+            Box::new(self.remaining.iter().copied())
+        };
+        // remaining stays sorted, since it only ever shrinks
+        let best = super::most_entropy(self.dictionary, &self.weights, &self.remaining, candidates);
+        best.map(|word| self.dictionary.words()[word].0).ok_or(Error::NoCandidates)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{prior::Counts, Dictionary, Error, Explanation, Guess, Guesser, Pattern, Prior, Word};
+
+/// Plays for the worst case: picks the word whose largest pattern bucket leaves the fewest
+/// remaining words, breaking ties by entropy. Against a host like `Absurdle` this bounds how
+/// many guesses a game can take.
+pub struct Minimax<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    /// Dictionary indices of the words still consistent with the history.
+    remaining: Vec<usize>,
+    /// How likely each dictionary word is, by index.
+    weights: Vec<f64>,
+}
+
+impl Minimax<'static> {
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Minimax<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts. Only the tie-break uses weights.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &dyn Prior<N>) -> Self {
+        Minimax {
+            dictionary,
+            remaining: (0..dictionary.len()).collect(),
+            weights: prior.weights(dictionary),
+        }
+    }
+}
+
+impl Default for Minimax<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    word: usize,
+    worst: usize,
+    goodness: f64,
+}
+
+impl<const N: usize> Guesser<N> for Minimax<'_, N> {
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn explain(&mut self, history: &[Guess<N>], top: usize) -> Option<Explanation<N>> {
+        let chosen = self.try_guess(history).ok()?;
+        let pool = self.remaining.iter().copied();
+        let searched = !history.is_empty() || super::minimax_opener(self.dictionary, &self.weights) != Some(chosen);
+        super::explanation(self.dictionary, &self.weights, &self.remaining, pool, chosen, searched, top)
+    }
+
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails once no dictionary word is left, which happens when the answer isn't one of them.
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, Error> {
+        // precomputed best opener for the built-in dictionary
+        if history.is_empty() {
+            if let Some(serai) = super::minimax_opener(self.dictionary, &self.weights) {
+                return Ok(serai);
+            }
+        }
+        let words = self.dictionary.words();
+        if let Some(last) = history.last() {
+            // update self.remaining based on history
+            super::retain_consistent(self.dictionary, &mut self.remaining, last);
+        }
+        let remaining_count: f64 = self.remaining.iter().map(|&answer| self.weights[answer]).sum();
+        let mut best: Option<Candidate> = None;
+
+        let mut in_pattern = vec![0usize; Pattern::<N>::COUNT];
+        let mut in_pattern_total = vec![0.0; Pattern::<N>::COUNT];
+        for &word in &self.remaining {
+            let row = self.dictionary.row(word);
+            in_pattern.fill(0);
+            for &answer in &self.remaining {
+                in_pattern[row.get(answer).index()] += 1;
+            }
+            // a correct guess ends the game, so its own bucket never counts against it
+            in_pattern[Pattern::<N>::ALL_CORRECT.index()] = 0;
+            let worst = in_pattern.iter().copied().max().unwrap_or(0);
+            if best.is_some_and(|c| worst > c.worst) {
+                continue;
+            }
+            let goodness = super::entropy(row, &self.remaining, &self.weights, remaining_count, &mut in_pattern_total);
+            if best.is_none_or(|c| worst < c.worst || goodness > c.goodness) {
+                best = Some(Candidate { word, worst, goodness });
+            }
+        }
+        best.map(|c| words[c.word].0).ok_or(Error::NoCandidates)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{multi::is_solved, prior::Counts, Dictionary, Error, Guess, MultiGuesser, Pattern, Prior, Word};
+
+/// Like `Matrix`, but for several boards: picks the word with the most entropy summed over the
+/// boards that are still open, out of the words that could still solve one of them.
+pub struct MultiEntropy<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    /// How likely each dictionary word is, by index.
+    weights: Vec<f64>,
+    /// Dictionary indices still consistent with each board, and how many guesses of that
+    /// board's history they account for.
+    boards: Vec<(Vec<usize>, usize)>,
+}
+
+impl MultiEntropy<'static> {
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> MultiEntropy<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &dyn Prior<N>) -> Self {
+        MultiEntropy {
+            dictionary,
+            weights: prior.weights(dictionary),
+            boards: Vec::new(),
+        }
+    }
+}
+
+impl Default for MultiEntropy<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> MultiGuesser<N> for MultiEntropy<'_, N> {
+    fn guess(&mut self, boards: &[Vec<Guess<N>>]) -> Word<N> {
+        self.try_guess(boards).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails once an open board has no dictionary word left, which happens when its answer isn't
+    /// one of them.
+    fn try_guess(&mut self, boards: &[Vec<Guess<N>>]) -> Result<Word<N>, Error> {
+        // precomputed best opener for the built-in dictionary
+        if boards.iter().all(|history| history.is_empty()) {
+            if let Some(tares) = super::opener(self.dictionary, &self.weights) {
+                return Ok(tares);
+            }
+        }
+        let words = self.dictionary.words();
+        if self.boards.len() != boards.len() {
+            self.boards = vec![((0..words.len()).collect(), 0); boards.len()];
+        }
+        for (history, (remaining, seen)) in boards.iter().zip(&mut self.boards) {
+            // update remaining based on the guesses this board got since the last call
+            for last in &history[*seen..] {
+                super::retain_consistent(self.dictionary, remaining, last);
+            }
+            *seen = history.len();
+        }
+        let open: Vec<&[usize]> = boards
+            .iter()
+            .zip(&self.boards)
+            .filter(|(history, _)| !is_solved(history))
+            .map(|(_, (remaining, _))| &remaining[..])
+            .collect();
+        if open.iter().any(|remaining| remaining.is_empty()) {
+            return Err(Error::NoCandidates);
+        }
+        // a board that's down to one word is a free win, and the guess still tells us about the others
+        if let Some(remaining) = open.iter().find(|remaining| remaining.len() == 1) {
+            return Ok(words[remaining[0]].0);
+        }
+
+        let mut candidates: Vec<usize> = open.iter().flat_map(|remaining| remaining.iter().copied()).collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        let totals: Vec<f64> = open.iter().map(|remaining| remaining.iter().map(|&answer| self.weights[answer]).sum()).collect();
+        let mut in_pattern_total = vec![0.0; Pattern::<N>::COUNT];
+        let mut best: Option<(usize, f64)> = None;
+        for word in candidates {
+            let row = self.dictionary.row(word);
+            let goodness: f64 = open
+                .iter()
+                .zip(&totals)
+                .map(|(remaining, &total)| super::entropy(row, remaining, &self.weights, total, &mut in_pattern_total))
+                .sum();
+            if best.is_none_or(|(_, g)| goodness > g) {
+                best = Some((word, goodness));
+            }
+        }
+        best.map(|(word, _)| words[word].0).ok_or(Error::NoCandidates)
+    }
+}
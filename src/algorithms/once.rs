@@ -1,78 +1,109 @@
-use crate::{Guesser, Guess, DICT, Correctness, Word};
-use std::{borrow::Cow};
-use once_cell::sync::OnceCell;
+use crate::{prior::Counts, Dictionary, Error, Prior, Explanation, Guesser, Guess, Correctness, Word};
+use std::borrow::Cow;
 
-static INITIAL: OnceCell<Vec<(&'static Word, usize)>> = OnceCell::new();
-
-pub struct Once{
-    remaining: Cow<'static, Vec<(&'static Word, usize)>>,
+pub struct Once<'d, const N: usize = 5> {
+    /// The precomputed best opener, when it holds for this dictionary and prior.
+    opener: Option<Word<N>>,
+    remaining: Cow<'d, [(Word<N>, usize)]>,
+    /// How likely each word of `remaining` is, in the same order.
+    weights: Cow<'d, [f64]>,
 }
 
-impl Once {
+#[allow(clippy::new_without_default)]
+impl Once<'static> {
     pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Once<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        Self::with_prior(dictionary, &Counts)
+    }
+
+    /// Weighs the words by `prior` instead of by their counts. Weights `prior` already holds
+    /// are borrowed rather than copied.
+    pub fn with_prior(dictionary: &'d Dictionary<N>, prior: &'d dyn Prior<N>) -> Self {
+        let weights = prior.borrow_weights(dictionary);
         Once {
-            remaining: Cow::Borrowed(INITIAL.get_or_init(|| {
-                Vec::from_iter(
-                    DICT.lines().map(
-                        |line| {
-                        let (word, count) = line.split_once(" ").expect("every line is word + space + occurance");
-                        let count:usize = count.parse().expect("every count is a number");
-                        (word.as_bytes().try_into().expect("5 letter words"), count)
-                        }
-                )) 
-            }))       
+            opener: super::opener(dictionary, &weights),
+            remaining: Cow::Borrowed(dictionary.words()),
+            weights,
         }
     }
 }
+
 #[derive(Debug, Clone, Copy)]
-struct Candidate {
-    word: &'static Word,
+struct Candidate<'a, const N: usize> {
+    word: &'a Word<N>,
     goodness: f64,
 }
 
-impl Guesser for Once {
-    fn guess(&mut self, history: &[Guess]) -> Word{
-        if history.is_empty(){
-            return *b"tares";
+impl<const N: usize> Once<'_, N> {
+    /// Narrows `remaining` by the newest guess and picks the word with the most entropy over it,
+    /// adding every word it weighs to `scored`.
+    fn choose(&mut self, history: &[Guess<N>], mut scored: Option<&mut Vec<(Word<N>, f64)>>) -> Word<N> {
+        // precomputed best opener for the built-in dictionary
+        if history.is_empty() {
+            if let Some(tares) = self.opener {
+                return tares;
+            }
         }
         if let Some(last) = history.last(){
-            // update self.remaining based on history
-
-            if matches!(self.remaining, Cow::Owned(_)) {
-                self.remaining.to_mut().retain(|(word, _)| last.matches(word));
+            // update self.remaining based on history, and the weights along with it
+            if let (Cow::Owned(remaining), Cow::Owned(weights)) = (&mut self.remaining, &mut self.weights) {
+                let mut kept = 0;
+                for i in 0..remaining.len() {
+                    if last.matches(&remaining[i].0) {
+                        remaining.swap(kept, i);
+                        weights.swap(kept, i);
+                        kept += 1;
+                    }
+                }
+                remaining.truncate(kept);
+                weights.truncate(kept);
             } else {
-                self.remaining = Cow::Owned(
-                    self.remaining
+                let (remaining, weights): (Vec<_>, Vec<_>) = self
+                    .remaining
                     .iter()
-                    .filter(|(word, _)| last.matches(word))
-                    .copied()
-                    .collect()
-                )
+                    .zip(&*self.weights)
+                    .filter(|((word, _), _)| last.matches(word))
+                    .map(|(&entry, &weight)| (entry, weight))
+                    .unzip();
+                self.remaining = Cow::Owned(remaining);
+                self.weights = Cow::Owned(weights);
             }
         }
-        let remaining_count: usize = self.remaining.iter().map(|(word, count)|{count}).sum();
-        let mut best:Option<Candidate>= None;
+        let remaining_count: f64 = self.weights.iter().sum();
+        let mut best:Option<Candidate<N>>= None;
 
-        for &(word, _) in &*self.remaining {
+        for (word, _) in &*self.remaining {
             // - SUM_i p_i * log(p_i)
             let mut goodness = 0.0;
             for pattern in Correctness::patterns(){
                 // considering a world where we did guess word and got pattern as the correctness.
                 // Now compute what then is left
-                let mut in_pattern_total = 0;
-                for (candidate, count) in &*self.remaining {
+                let mut in_pattern_total = 0.0;
+                for ((candidate, _), weight) in self.remaining.iter().zip(&*self.weights) {
                     let g = Guess {
                         word:Cow::Borrowed(word),
                         mask:pattern
                     };
                     if g.matches(candidate) {
-                        in_pattern_total += count;
+                        in_pattern_total += weight;
                     } 
                 }
-                if in_pattern_total == 0 {continue;}
-                let p_of_this_pattern = in_pattern_total as f64 / remaining_count as f64;
+                if in_pattern_total == 0.0 {continue;}
+                let p_of_this_pattern = in_pattern_total / remaining_count;
                 goodness += -(p_of_this_pattern * p_of_this_pattern.log2());
             }
+            if let Some(scored) = &mut scored {
+                scored.push((*word, goodness));
+            }
             if let Some(c) = best {
                 // is this one better?
                 if goodness > c.goodness {
@@ -86,3 +117,19 @@ impl Guesser for Once {
     }
 }
 
+impl<const N: usize> Guesser<N> for Once<'_, N> {
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn explain(&mut self, history: &[Guess<N>], top: usize) -> Option<Explanation<N>> {
+        let mut scored = Vec::new();
+        let chosen = self.choose(history, Some(&mut scored));
+        let remaining = self.remaining.iter().zip(&*self.weights).map(|((word, _), &weight)| (word, weight));
+        Some(Explanation::from_scored(chosen, remaining, scored, top))
+    }
+
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.choose(history, None)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{Correctness, Guess, HardModeViolation, Word};
+
+/// Everything a history says about the answer, packed so that testing a word takes a few
+/// integer operations instead of a mask per guess.
+///
+/// A word passes `matches` exactly when every guess of the history would get its mask with that
+/// word as the answer, i.e. when `Guess::matches` holds for all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints<const N: usize = 5> {
+    /// Letters each position can still hold, one bit per letter (`a` is bit 0).
+    allowed: [u32; N],
+    /// Letters revealed green at each position. More than one means no word fits.
+    green: [u32; N],
+    /// Fewest times each letter must appear.
+    min: [u8; 26],
+    /// Most times each letter can appear.
+    max: [u8; 26],
+    /// Letters with a `min` or `max` that rules something out, so `matches` only counts those.
+    counted: u32,
+}
+
+fn bit(letter: u8) -> u32 {
+    1 << (letter - b'a')
+}
+
+impl<const N: usize> Constraints<N> {
+    const ANY: u32 = (1 << 26) - 1;
+
+    /// Constraints that every word meets.
+    pub fn new() -> Self {
+        Constraints {
+            allowed: [Self::ANY; N],
+            green: [0; N],
+            min: [0; 26],
+            max: [N as u8; 26],
+            counted: 0,
+        }
+    }
+
+    pub fn from_history(history: &[Guess<N>]) -> Self {
+        let mut constraints = Self::new();
+        for guess in history {
+            constraints.add(&guess.word, &guess.mask);
+        }
+        constraints
+    }
+
+    /// Narrows the constraints down to the words that give `mask` when `guess` is played.
+    pub fn add(&mut self, guess: &Word<N>, mask: &[Correctness; N]) {
+        // (letters shown yellow or green, whether any copy was gray) per letter
+        let mut shown = [(0u8, false); 26];
+        for (i, (&letter, &c)) in guess.iter().zip(mask).enumerate() {
+            let l = (letter - b'a') as usize;
+            match c {
+                Correctness::Correct => {
+                    self.allowed[i] &= bit(letter);
+                    self.green[i] |= bit(letter);
+                    shown[l].0 += 1;
+                }
+                Correctness::Misplaced => {
+                    // yellows go to the first copies that aren't green, so none can follow a gray
+                    if shown[l].1 {
+                        self.allowed = [0; N];
+                    }
+                    self.allowed[i] &= !bit(letter);
+                    shown[l].0 += 1;
+                }
+                Correctness::Wrong => {
+                    self.allowed[i] &= !bit(letter);
+                    shown[l].1 = true;
+                }
+            }
+        }
+        for (l, &(count, gray)) in shown.iter().enumerate() {
+            self.min[l] = self.min[l].max(count);
+            if gray {
+                self.max[l] = self.max[l].min(count);
+            }
+            if self.min[l] > 0 || (self.max[l] as usize) < N {
+                self.counted |= 1 << l;
+            }
+        }
+    }
+
+    /// Whether `word` could still be the answer.
+    pub fn matches(&self, word: &Word<N>) -> bool {
+        if word.iter().zip(&self.allowed).any(|(&letter, &allowed)| allowed & bit(letter) == 0) {
+            return false;
+        }
+        if self.counted == 0 {
+            return true;
+        }
+        let mut counts = [0u8; 26];
+        for &letter in word {
+            counts[(letter - b'a') as usize] += 1;
+        }
+        let mut letters = self.counted;
+        while letters != 0 {
+            let l = letters.trailing_zeros() as usize;
+            letters &= letters - 1;
+            if counts[l] < self.min[l] || counts[l] > self.max[l] {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The hard mode rules, a looser test than `matches`: greens stay in place and revealed
+    /// letters are used at least as often as a single guess revealed them.
+    pub fn check_hard_mode(&self, word: &Word<N>) -> Result<(), HardModeViolation> {
+        for (position, (&letter, &green)) in word.iter().zip(&self.green).enumerate() {
+            let missing = green & !bit(letter);
+            if missing != 0 {
+                let letter = b'a' + missing.trailing_zeros() as u8;
+                return Err(HardModeViolation::MissingGreen { position, letter });
+            }
+        }
+        for (letter, &min) in (b'a'..=b'z').zip(&self.min) {
+            if min > 0 && word.iter().filter(|&&l| l == letter).count() < min as usize {
+                return Err(HardModeViolation::MissingLetter { letter, count: min as usize });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Constraints<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Constraints;
+    use crate::{Correctness, Dictionary, Guess, Pattern};
+    use std::borrow::Cow;
+
+    #[test]
+    fn agrees_with_compute() {
+        let words = Dictionary::embedded().words();
+        let answers: Vec<_> = words.iter().step_by(331).map(|(answer, _)| answer).collect();
+        // every guess, with the masks it actually gets against the sampled answers
+        for (guess, _) in words {
+            let mut patterns: Vec<_> = answers.iter().map(|answer| Pattern::compute(answer, guess)).collect();
+            patterns.sort_by_key(|pattern| pattern.id());
+            patterns.dedup();
+            for mask in patterns.into_iter().map(Pattern::mask) {
+                let mut constraints = Constraints::new();
+                constraints.add(guess, &mask);
+                for &word in &answers {
+                    assert_eq!(
+                        constraints.matches(word),
+                        Correctness::compute(word, guess) == mask,
+                        "{} with {:?} against {}",
+                        String::from_utf8_lossy(guess),
+                        mask,
+                        String::from_utf8_lossy(word)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn whole_history() {
+        let words = Dictionary::embedded().words();
+        for answer in [b"eager", b"abbey", b"sissy"] {
+            let history: Vec<_> = [b"tares", b"geese", b"essay"]
+                .iter()
+                .map(|&guess| Guess { word: Cow::Borrowed(guess), mask: Correctness::compute(answer, guess) })
+                .collect();
+            let constraints = Constraints::from_history(&history);
+            for (word, _) in words {
+                assert_eq!(constraints.matches(word), history.iter().all(|g| g.matches(word)));
+            }
+            assert!(constraints.matches(answer));
+        }
+        assert!(Constraints::<5>::new().matches(b"zzzzz"));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Days from 1970-01-01 to a date of the (proleptic) Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // shift the year to start in March, so the leap day comes last
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The puzzle number of a date. Puzzle 0 was on 2021-06-19, and its answer is the first word
+/// of `answers.txt`.
+pub fn day_number(year: i64, month: u32, day: u32) -> i64 {
+    days_from_civil(year, month, day) - days_from_civil(2021, 6, 19)
+}
+
+/// The puzzle number of a `YYYY-MM-DD` date.
+pub fn parse_date(s: &str) -> Option<i64> {
+    let mut fields = s.trim().splitn(3, '-');
+    let year = fields.next()?.parse().ok()?;
+    let month = fields.next()?.parse().ok()?;
+    let day = fields.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(day_number(year, month, day))
+}
+
+/// Today's puzzle number, going by UTC.
+pub fn today() -> i64 {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    (secs / 86400) as i64 - days_from_civil(2021, 6, 19)
+}
+
+/// Which of `len` answers belongs to puzzle `day`. The list wraps around once it runs out.
+pub fn answer_index(day: i64, len: usize) -> Result<usize, Error> {
+    if len == 0 {
+        return Err(Error::NoAnswers);
+    }
+    Ok(day.rem_euclid(len as i64) as usize)
+}
+
+/// A random looking index below `len`, always the same for the same `seed` (splitmix64).
+pub fn seeded_index(seed: u64, len: usize) -> Result<usize, Error> {
+    if len == 0 {
+        return Err(Error::NoAnswers);
+    }
+    let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^= z >> 31;
+    Ok((z % len as u64) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{answer_index, day_number, parse_date, seeded_index};
+    use crate::Error;
+
+    #[test]
+    fn dates() {
+        assert_eq!(day_number(2021, 6, 19), 0);
+        assert_eq!(parse_date("2022-01-05"), Some(200));
+        assert_eq!(parse_date("2024-02-29"), Some(985));
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2021-13-01"), None);
+        assert_eq!(parse_date("yesterday"), None);
+        assert_eq!(answer_index(-1, 10), Ok(9));
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(seeded_index(42, 2309), seeded_index(42, 2309));
+        assert_ne!(seeded_index(1, 1 << 20), seeded_index(2, 1 << 20));
+        assert!((0..100).all(|seed| seeded_index(seed, 7).unwrap() < 7));
+    }
+
+    #[test]
+    fn no_answers() {
+        assert_eq!(answer_index(200, 0), Err(Error::NoAnswers));
+        assert_eq!(seeded_index(42, 0), Err(Error::NoAnswers));
+    }
+}
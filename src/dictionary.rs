@@ -0,0 +1,228 @@
+use crate::{Error, Pattern, Word};
+use once_cell::sync::OnceCell;
+use std::{collections::HashMap, fs::File, io::Read, marker::PhantomData, path::Path};
+
+const DICT: &str = include_str!("../dictionary.txt");
+const ANSWERS: &str = include_str!("../answers.txt");
+
+static EMBEDDED: OnceCell<Dictionary> = OnceCell::new();
+static EMBEDDED_ANSWERS: OnceCell<Dictionary> = OnceCell::new();
+
+/// A word list with occurance counts, plus a lazily built table of the pattern every word
+/// produces against every other one.
+///
+/// Rows of the table are keyed by guess index and built the first time that guess is scored,
+/// so only the guesses a guesser actually considers ever get computed. The embedded
+/// dictionaries are process-wide, so their tables are shared by every game. Up to 5 letters a
+/// pattern fits in a byte, which is how rows store them; longer words take two.
+pub struct Dictionary<const N: usize = 5> {
+    words: Vec<(Word<N>, usize)>,
+    index: HashMap<Word<N>, usize>,
+    rows: Vec<OnceCell<Ids>>,
+}
+
+/// The pattern ids of one row of the table.
+enum Ids {
+    Narrow(Box<[u8]>),
+    Wide(Box<[u16]>),
+}
+
+/// Patterns of one guess against every answer of a `Dictionary`, see `Dictionary::row`.
+#[derive(Clone, Copy)]
+pub struct Row<'a, const N: usize = 5> {
+    ids: &'a Ids,
+    pattern: PhantomData<Pattern<N>>,
+}
+
+impl<const N: usize> Row<'_, N> {
+    /// The pattern the guess gets against dictionary word `answer`.
+    pub fn get(&self, answer: usize) -> Pattern<N> {
+        match self.ids {
+            Ids::Narrow(ids) => Pattern::from_id(ids[answer] as u16),
+            Ids::Wide(ids) => Pattern::from_id(ids[answer]),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self.ids {
+            Ids::Narrow(ids) => ids.len(),
+            Ids::Wide(ids) => ids.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The built-in lists only have 5 letter words.
+impl Dictionary {
+    /// The `dictionary.txt` compiled into the binary.
+    pub fn embedded() -> &'static Self {
+        Self::try_embedded().expect("embedded dictionary is well-formed")
+    }
+
+    pub fn try_embedded() -> Result<&'static Self, Error> {
+        EMBEDDED.get_or_try_init(|| Self::parse(DICT))
+    }
+
+    /// The `answers.txt` compiled into the binary.
+    pub fn embedded_answers() -> &'static Self {
+        EMBEDDED_ANSWERS
+            .get_or_try_init(|| Self::parse(ANSWERS))
+            .expect("embedded answers are well-formed")
+    }
+}
+
+impl<const N: usize> Dictionary<N> {
+    /// Parses one word per line, either as `word count` like `dictionary.txt` or as a bare
+    /// word like `answers.txt` (which counts once). Blank lines are skipped.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let mut words = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let malformed = || Error::MalformedDictionaryLine { line: i + 1, content: line.to_owned() };
+            let mut fields = line.split_whitespace();
+            let Some(word) = fields.next() else { continue };
+            let count = match fields.next() {
+                Some(count) => count.parse().map_err(|_| malformed())?,
+                None => 1,
+            };
+            if fields.next().is_some() {
+                return Err(malformed());
+            }
+            let word: Word<N> = word.as_bytes().try_into().map_err(|_| Error::WrongWordLength {
+                word: word.to_owned(),
+                expected: N,
+            })?;
+            if !word.iter().all(u8::is_ascii_lowercase) {
+                return Err(Error::InvalidLetters { line: i + 1, word: String::from_utf8_lossy(&word).into_owned() });
+            }
+            words.push((word, count));
+        }
+        Ok(Self::from_words(words))
+    }
+
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, Error> {
+        let mut s = String::new();
+        reader.read_to_string(&mut s)?;
+        Self::parse(&s)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    pub fn from_words(words: Vec<(Word<N>, usize)>) -> Self {
+        let index = words.iter().enumerate().map(|(i, &(word, _))| (word, i)).collect();
+        let rows = (0..words.len()).map(|_| OnceCell::new()).collect();
+        Dictionary { words, index, rows }
+    }
+
+    /// Every word with its occurance count, in index order.
+    pub fn words(&self) -> &[(Word<N>, usize)] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, word: &Word<N>) -> bool {
+        self.index.contains_key(word)
+    }
+
+    /// Whether this is the very dictionary `embedded` returns, rather than one that was parsed
+    /// separately.
+    pub(crate) fn is_embedded(&self) -> bool {
+        EMBEDDED
+            .get()
+            .is_some_and(|embedded| std::ptr::eq(self as *const Self as *const (), embedded as *const Dictionary as *const ()))
+    }
+
+    pub fn index_of(&self, word: &Word<N>) -> Option<usize> {
+        self.index.get(word).copied()
+    }
+
+    /// Patterns of word `guess` against every answer, indexed by answer.
+    pub fn row(&self, guess: usize) -> Row<'_, N> {
+        let ids = self.rows[guess].get_or_init(|| {
+            let guess = &self.words[guess].0;
+            let ids = self.words.iter().map(|(answer, _)| Pattern::compute(answer, guess).id());
+            if Pattern::<N>::COUNT <= 256 {
+                Ids::Narrow(ids.map(|id| id as u8).collect())
+            } else {
+                Ids::Wide(ids.collect())
+            }
+        });
+        Row { ids, pattern: PhantomData }
+    }
+
+    pub fn pattern(&self, guess: usize, answer: usize) -> Pattern<N> {
+        self.row(guess).get(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Correctness;
+
+    #[test]
+    fn row_agrees_with_compute() {
+        let d = Dictionary::embedded();
+        let guess = d.index_of(b"tares").unwrap();
+        for (answer, (word, _)) in d.words().iter().enumerate().step_by(97) {
+            assert_eq!(
+                d.pattern(guess, answer),
+                Pattern::from(Correctness::compute(word, b"tares"))
+            );
+        }
+    }
+
+    #[test]
+    fn wide_rows() {
+        // 3^6 patterns don't fit in the byte per pattern 5 letter rows use
+        let d = Dictionary::<6>::parse("abacus\nzebras\nbanana\nsaucer\n").unwrap();
+        let words = d.words();
+        for guess in 0..d.len() {
+            assert_eq!(d.row(guess).len(), d.len());
+            for answer in 0..d.len() {
+                assert_eq!(d.pattern(guess, answer), Pattern::compute(&words[answer].0, &words[guess].0));
+            }
+        }
+    }
+
+    #[test]
+    fn formats() {
+        let d = Dictionary::<5>::parse("aahed 49650\n\naargh\n").unwrap();
+        assert_eq!(d.words(), &[(*b"aahed", 49650), (*b"aargh", 1)]);
+        assert_eq!(Dictionary::embedded_answers().len(), 2309);
+        let d = Dictionary::<6>::parse("abacus 3\nzebras\n").unwrap();
+        assert_eq!(d.words(), &[(*b"abacus", 3), (*b"zebras", 1)]);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            Dictionary::<5>::parse("aahed 49650\naalii many\n").err(),
+            Some(Error::MalformedDictionaryLine { line: 2, content: "aalii many".to_owned() })
+        );
+        assert_eq!(
+            Dictionary::<5>::parse("aahed 49650\naargh 38069\naah 5\n").err(),
+            Some(Error::WrongWordLength { word: "aah".to_owned(), expected: 5 })
+        );
+        // letters are used to index per-letter tables, so anything else has to go at load time
+        assert_eq!(
+            Dictionary::<5>::parse("aahed\nAargh\n").err(),
+            Some(Error::InvalidLetters { line: 2, word: "Aargh".to_owned() })
+        );
+        assert_eq!(
+            Dictionary::<5>::parse("café\n").err(),
+            Some(Error::InvalidLetters { line: 1, word: "café".to_owned() })
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::HardModeViolation;
+use std::{fmt, io};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The guess isn't in the dictionary.
+    InvalidGuess(String),
+    /// A dictionary line (1-based) is neither `word count` nor a bare word.
+    MalformedDictionaryLine { line: usize, content: String },
+    WrongWordLength { word: String, expected: usize },
+    /// A dictionary word (on a 1-based line) has something other than the letters `a` to `z`.
+    InvalidLetters { line: usize, word: String },
+    /// The game already used up all of its guesses.
+    TooManyGuesses { limit: usize },
+    HardMode(HardModeViolation),
+    /// Reading a word list failed.
+    Io(io::ErrorKind, String),
+    /// A stored decision tree doesn't have the expected shape.
+    MalformedTree(String),
+    /// A guesser that follows a fixed strategy was asked about a game outside of it.
+    LeftTree(String),
+    /// No dictionary word is consistent with the history, e.g. because the answer isn't one.
+    NoCandidates,
+    /// A puzzle was asked for from an empty answer list.
+    NoAnswers,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidGuess(word) => {
+                write!(f, "guess '{}' is not in dictionary", word)
+            }
+            Self::MalformedDictionaryLine { line, content } => {
+                write!(f, "dictionary line {} is not a word or word + space + occurance: '{}'", line, content)
+            }
+            Self::WrongWordLength { word, expected } => {
+                write!(f, "'{}' is not {} characters long", word, expected)
+            }
+            Self::InvalidLetters { line, word } => {
+                write!(f, "dictionary line {} has '{}', but words can only use the letters a to z", line, word)
+            }
+            Self::TooManyGuesses { limit } => write!(f, "no guesses left after {}", limit),
+            Self::HardMode(violation) => write!(f, "hard mode violation: {}", violation),
+            Self::Io(_, message) => write!(f, "could not read word list: {}", message),
+            Self::MalformedTree(why) => write!(f, "malformed decision tree: {}", why),
+            Self::LeftTree(why) => write!(f, "game left the decision tree: {}", why),
+            Self::NoCandidates => write!(f, "no dictionary word fits every guess so far"),
+            Self::NoAnswers => write!(f, "the answer list is empty"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::HardMode(violation) => Some(violation),
+            _ => None,
+        }
+    }
+}
+
+impl From<HardModeViolation> for Error {
+    fn from(violation: HardModeViolation) -> Self {
+        Self::HardMode(violation)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e.kind(), e.to_string())
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{algorithms::entropy, Dictionary, Pattern, Word};
+use std::fmt;
+
+/// Why a guesser picked its word, see `Guesser::explain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation<const N: usize = 5> {
+    /// The word the guesser went with.
+    pub chosen: Word<N>,
+    /// Words still consistent with the history.
+    pub remaining: usize,
+    /// Their total weight under the guesser's prior.
+    pub remaining_weight: f64,
+    /// The words the guesser weighed with the most entropy over the remaining words, best first.
+    /// `chosen` isn't always first: a guesser may rank by something other than entropy.
+    pub candidates: Vec<(Word<N>, f64)>,
+    /// How `chosen` splits the remaining words: each pattern it can get, with how many words and
+    /// how much weight give it, heaviest first.
+    pub buckets: Vec<(Pattern<N>, usize, f64)>,
+}
+
+impl<const N: usize> Explanation<N> {
+    /// Ranks the dictionary indices in `pool` by entropy over `remaining`, keeping the `top`
+    /// best, and buckets `remaining` by the pattern `chosen` gets.
+    pub(crate) fn rank(
+        dictionary: &Dictionary<N>,
+        weights: &[f64],
+        remaining: &[usize],
+        pool: impl Iterator<Item = usize>,
+        chosen: usize,
+        top: usize,
+    ) -> Self {
+        let remaining_weight: f64 = remaining.iter().map(|&answer| weights[answer]).sum();
+        let mut in_pattern_total = vec![0.0; Pattern::<N>::COUNT];
+        let mut candidates: Vec<(Word<N>, f64)> = pool
+            .map(|word| {
+                let row = dictionary.row(word);
+                let goodness = entropy(row, remaining, weights, remaining_weight, &mut in_pattern_total);
+                // a bucket with everything in it scores -0
+                (dictionary.words()[word].0, goodness + 0.0)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates.truncate(top);
+
+        let row = dictionary.row(chosen);
+        let mut in_pattern = vec![(0, 0.0); Pattern::<N>::COUNT];
+        for &answer in remaining {
+            let bucket = &mut in_pattern[row.get(answer).index()];
+            bucket.0 += 1;
+            bucket.1 += weights[answer];
+        }
+
+        Explanation {
+            chosen: dictionary.words()[chosen].0,
+            remaining: remaining.len(),
+            remaining_weight,
+            candidates,
+            buckets: buckets(in_pattern),
+        }
+    }
+
+    /// For the guessers that keep words rather than dictionary indices: `scored` is every word
+    /// they weighed with its entropy, and `remaining` the words still consistent with the history
+    /// with their weights. With nothing `scored`, as for an opener, `chosen` is listed on its own.
+    pub(crate) fn from_scored<'a>(
+        chosen: Word<N>,
+        remaining: impl Iterator<Item = (&'a Word<N>, f64)>,
+        mut scored: Vec<(Word<N>, f64)>,
+        top: usize,
+    ) -> Self {
+        let (mut count, mut remaining_weight) = (0, 0.0);
+        let mut in_pattern = vec![(0, 0.0); Pattern::<N>::COUNT];
+        for (answer, weight) in remaining {
+            let bucket = &mut in_pattern[Pattern::compute(answer, &chosen).index()];
+            bucket.0 += 1;
+            bucket.1 += weight;
+            count += 1;
+            remaining_weight += weight;
+        }
+        let buckets = buckets(in_pattern);
+        if scored.is_empty() {
+            // - SUM_i p_i * log(p_i), over the buckets
+            let goodness: f64 = buckets
+                .iter()
+                .map(|&(_, _, weight)| weight / remaining_weight)
+                .filter(|&p| p > 0.0)
+                .map(|p| -p * p.log2())
+                .sum();
+            scored.push((chosen, goodness + 0.0));
+        }
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(top);
+
+        Explanation { chosen, remaining: count, remaining_weight, candidates: scored, buckets }
+    }
+}
+
+/// The patterns of `in_pattern` that any word gets, heaviest first.
+fn buckets<const N: usize>(in_pattern: Vec<(usize, f64)>) -> Vec<(Pattern<N>, usize, f64)> {
+    let mut buckets: Vec<_> = Pattern::all()
+        .zip(in_pattern)
+        .filter(|&(_, (count, _))| count > 0)
+        .map(|(pattern, (count, weight))| (pattern, count, weight))
+        .collect();
+    buckets.sort_by(|a, b| b.2.total_cmp(&a.2));
+    buckets
+}
+
+/// The candidates, then the biggest buckets of the chosen word.
+impl<const N: usize> fmt::Display for Explanation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const BUCKETS: usize = 5;
+        writeln!(
+            f,
+            "{} candidates (weight {:.4e}), picked {}",
+            self.remaining,
+            self.remaining_weight,
+            String::from_utf8_lossy(&self.chosen)
+        )?;
+        for (word, goodness) in &self.candidates {
+            let marker = if *word == self.chosen { '*' } else { ' ' };
+            writeln!(f, "  {} {} {:.4} bits", marker, String::from_utf8_lossy(word), goodness)?;
+        }
+        write!(f, "  {} buckets", self.buckets.len())?;
+        for (pattern, count, weight) in self.buckets.iter().take(BUCKETS) {
+            write!(f, "\n    {} {:>5} words, p {:.4}", pattern, count, weight / self.remaining_weight)?;
+        }
+        if self.buckets.len() > BUCKETS {
+            write!(f, "\n    ... {} more", self.buckets.len() - BUCKETS)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{algorithms::{Matrix, Once, VecRem}, Correctness, Dictionary, Guess, Guesser};
+    use std::borrow::Cow;
+
+    #[test]
+    fn matrix() {
+        let d = Dictionary::<5>::parse("catch 5\nhatch 4\nlatch 3\nmatch 2\nwatch 1\nbingo 1\n").unwrap();
+        let history = [Guess { word: Cow::Borrowed(b"bingo"), mask: Correctness::compute(b"latch", b"bingo") }];
+        let mut explained = Matrix::with_dictionary(&d);
+        let explanation = explained.explain(&history[..0], 3).unwrap();
+        assert_eq!(explanation.remaining, 6);
+        assert_eq!(explanation.remaining_weight, 16.0);
+        let explanation = explained.explain(&history, 3).unwrap();
+        assert_eq!(explanation.chosen, Matrix::with_dictionary(&d).guess(&history));
+        assert_eq!(explanation.remaining, 5);
+        assert_eq!(explanation.candidates.len(), 3);
+        assert!(explanation.candidates.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(explanation.buckets.iter().map(|b| b.1).sum::<usize>(), 5);
+        assert_eq!(explanation.buckets[0].2, 15.0 - explanation.buckets[1..].iter().map(|b| b.2).sum::<f64>());
+
+        let mut g = guesser!(|_history| { *b"catch" });
+        assert!(g.explain(&history, 3).is_none());
+    }
+
+    #[test]
+    fn scored() {
+        let d = Dictionary::<5>::parse("catch 5\nhatch 4\nlatch 3\nmatch 2\nwatch 1\nbingo 1\n").unwrap();
+        let history = [Guess { word: Cow::Borrowed(b"bingo"), mask: Correctness::compute(b"latch", b"bingo") }];
+        let mut explained = VecRem::with_dictionary(&d);
+        assert_eq!(explained.explain(&history[..0], 3).unwrap().remaining, 6);
+        let explanation = explained.explain(&history, 3).unwrap();
+        assert_eq!(explanation.chosen, VecRem::with_dictionary(&d).guess(&history));
+        assert_eq!(explanation.remaining, 5);
+        assert_eq!(explanation.remaining_weight, 15.0);
+        assert_eq!(explanation.candidates[0].0, explanation.chosen);
+        assert!(explanation.candidates.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(explanation.buckets.iter().map(|b| b.1).sum::<usize>(), 5);
+
+        // the opener isn't searched for, but scores the same as when matrix explains it
+        let opener = Once::new().explain(&[], 3).unwrap();
+        let matrix = Matrix::new().explain(&[], 3).unwrap();
+        assert_eq!(opener.chosen, *b"tares");
+        assert_eq!(opener.candidates.len(), 1);
+        assert!((opener.candidates[0].1 - matrix.candidates[0].1).abs() < 1e-9);
+        assert_eq!(opener.buckets.len(), matrix.buckets.len());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Correctness, Pattern, Word};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// One guess of a game and the mask it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn<const N: usize = 5> {
+    pub word: Word<N>,
+    pub mask: [Correctness; N],
+    /// How many candidates the guesser still had when it picked `word`, if it says.
+    pub remaining: Option<usize>,
+    /// Time the guesser took to pick `word`.
+    pub elapsed: Duration,
+}
+
+/// Everything that happened in a game played by `Wordle::try_play`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult<const N: usize = 5> {
+    pub answer: Word<N>,
+    /// Every guess in order, including the winning one.
+    pub turns: Vec<Turn<N>>,
+    pub won: bool,
+}
+
+impl<const N: usize> GameResult<N> {
+    /// How many guesses it took, if the game was won.
+    pub fn guesses(&self) -> Option<usize> {
+        self.won.then_some(self.turns.len())
+    }
+
+    /// Total time the guesser spent thinking.
+    pub fn elapsed(&self) -> Duration {
+        self.turns.iter().map(|turn| turn.elapsed).sum()
+    }
+
+    /// One self-contained record per game, for `--format json`.
+    pub fn to_json(&self) -> Value {
+        let turns: Vec<_> = self
+            .turns
+            .iter()
+            .map(|turn| {
+                json!({
+                    "word": String::from_utf8_lossy(&turn.word),
+                    "mask": format!("{:#}", Pattern::from(turn.mask)),
+                    "remaining": turn.remaining,
+                    "elapsed_ms": millis(turn.elapsed),
+                })
+            })
+            .collect();
+        json!({
+            "answer": String::from_utf8_lossy(&self.answer),
+            "outcome": if self.won { "won" } else { "lost" },
+            "guesses": self.guesses(),
+            "turns": turns,
+            "elapsed_ms": millis(self.elapsed()),
+        })
+    }
+
+    pub const CSV_HEADER: &'static str = "answer,outcome,guesses,words,masks,elapsed_ms";
+
+    /// One row under `CSV_HEADER`, for `--format csv`. Words and masks are space separated.
+    pub fn to_csv(&self) -> String {
+        let words: Vec<_> = self.turns.iter().map(|turn| String::from_utf8_lossy(&turn.word)).collect();
+        let masks: Vec<_> = self.turns.iter().map(|turn| format!("{:#}", Pattern::from(turn.mask))).collect();
+        format!(
+            "{},{},{},{},{},{}",
+            String::from_utf8_lossy(&self.answer),
+            if self.won { "won" } else { "lost" },
+            self.guesses().map_or(String::new(), |n| n.to_string()),
+            words.join(" "),
+            masks.join(" "),
+            millis(self.elapsed()),
+        )
+    }
+}
+
+pub(crate) fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameResult, Turn};
+    use crate::Correctness;
+    use std::time::Duration;
+
+    fn result() -> GameResult {
+        let turn = |word: &[u8; 5], remaining| Turn {
+            word: *word,
+            mask: Correctness::compute(b"right", word),
+            remaining: Some(remaining),
+            elapsed: Duration::from_millis(2),
+        };
+        GameResult { answer: *b"right", turns: vec![turn(b"wrong", 100), turn(b"right", 1)], won: true }
+    }
+
+    #[test]
+    fn json() {
+        let json = result().to_json();
+        assert_eq!(json["answer"], "right");
+        assert_eq!(json["outcome"], "won");
+        assert_eq!(json["guesses"], 2);
+        assert_eq!(json["turns"][0]["word"], "wrong");
+        assert_eq!(json["turns"][0]["mask"], ".y..y");
+        assert_eq!(json["turns"][1]["remaining"], 1);
+        assert_eq!(json["elapsed_ms"], 4.0);
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(result().to_csv(), "right,won,2,wrong right,.y..y ggggg,4");
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{Constraints, Guess, Word};
+use std::fmt;
+
+/// Why a guess isn't allowed in hard mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardModeViolation {
+    /// A letter revealed green wasn't kept in its position (0-based).
+    MissingGreen { position: usize, letter: u8 },
+    /// A letter revealed green or yellow wasn't used at least `count` times.
+    MissingLetter { letter: u8, count: usize },
+}
+
+impl fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::MissingGreen { position, letter } => {
+                write!(f, "letter {} must be '{}'", position + 1, letter as char)
+            }
+            Self::MissingLetter { letter, count: 1 } => {
+                write!(f, "guess must contain '{}'", letter as char)
+            }
+            Self::MissingLetter { letter, count } => {
+                write!(f, "guess must contain '{}' {} times", letter as char, count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HardModeViolation {}
+
+/// Checks `guess` against the hard mode rules: every green revealed so far stays in place, and
+/// every revealed letter is used again (as many times as it was revealed in a single guess).
+pub fn check_hard_mode<const N: usize>(history: &[Guess<N>], guess: &Word<N>) -> Result<(), HardModeViolation> {
+    Constraints::from_history(history).check_hard_mode(guess)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_hard_mode, HardModeViolation};
+    use crate::Guess;
+    use std::borrow::Cow;
+
+    #[test]
+    fn rules() {
+        let history = [Guess { word: Cow::Borrowed(b"tares"), mask: mask![C W M W W] }];
+        assert_eq!(check_hard_mode(&history, b"thorn"), Ok(()));
+        assert_eq!(
+            check_hard_mode(&history, b"north"),
+            Err(HardModeViolation::MissingGreen { position: 0, letter: b't' })
+        );
+        assert_eq!(
+            check_hard_mode(&history, b"tonic"),
+            Err(HardModeViolation::MissingLetter { letter: b'r', count: 1 })
+        );
+    }
+
+    #[test]
+    fn repeated_letters() {
+        let history = [Guess { word: Cow::Borrowed(b"eerie"), mask: mask![M M W W W] }];
+        assert_eq!(check_hard_mode(&history, b"geese"), Ok(()));
+        assert_eq!(
+            check_hard_mode(&history, b"hoped"),
+            Err(HardModeViolation::MissingLetter { letter: b'e', count: 2 })
+        );
+    }
+}
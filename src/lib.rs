@@ -1,49 +1,178 @@
-use std::{collections::HashSet, borrow::Cow};
+use std::{borrow::Cow, time::Instant};
+
+#[cfg(test)]
+macro_rules! guesser {
+    (|$history: ident| $impl: block) => {{
+        struct G;
+        impl crate::Guesser for G {
+            #[allow(unused_variables)]
+            fn guess(&mut self, $history: &[Guess]) -> $crate::Word {
+                $impl
+            }
+        }
+        G        
+    }};
+}
+
+#[cfg(test)]
+macro_rules! mask {
+    (M) => {crate::Correctness::Misplaced};
+    (C) => {crate::Correctness::Correct};
+    (W) => {crate::Correctness::Wrong};
+    ($($c:tt)+) => {[$(mask!($c)),+]};
+}
 
 pub mod algorithms;
-const DICT:&str = include_str!("../dictionary.txt");
-pub type Word = [u8; 5];
-pub trait Guesser {
-    fn guess(&mut self, history: &[Guess]) -> Word;
+pub mod prior;
+pub use prior::Prior;
+mod dictionary;
+pub use dictionary::{Dictionary, Row};
+mod pattern;
+pub use pattern::{ParsePatternError, Pattern};
+mod hard_mode;
+pub use hard_mode::{check_hard_mode, HardModeViolation};
+mod constraints;
+pub use constraints::Constraints;
+mod error;
+pub use error::Error;
+mod game;
+pub use game::{GameResult, Turn};
+mod stats;
+pub use stats::Summary;
+mod simulate;
+pub use simulate::simulate;
+mod tree;
+pub use tree::{DecisionTree, TreeGuesser};
+mod solver;
+pub use solver::Solver;
+mod absurdle;
+pub use absurdle::Absurdle;
+pub mod multi;
+pub use multi::{MultiGuesser, MultiResult, MultiWordle};
+pub mod daily;
+mod share;
+pub use share::{ParseShareError, Share, Theme};
+pub mod reverse;
+mod review;
+pub use review::{review, Review, Step};
+mod explain;
+pub use explain::Explanation;
+/// A guessable word. Everything defaults to classic 5 letter Wordle, but takes the word length
+/// as a const generic for Lingo-style variants.
+pub type Word<const N: usize = 5> = [u8; N];
+/// How many guesses `Wordle::play` allows before giving up.
+pub const MAX_GUESSES: usize = 7;
+
+/// Picks the next word given everything guessed so far.
+///
+/// The guessers in `algorithms` only ever pick words consistent with every entry in `history`,
+/// which always satisfies the hard mode rules, unless they're told to probe.
+pub trait Guesser<const N: usize = 5> {
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N>;
+
+    /// Like `guess`, for guessers that can get stuck, e.g. when the history isn't one they
+    /// know how to continue. `Wordle::try_play` reports the error instead of panicking.
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, Error> {
+        Ok(self.guess(history))
+    }
+
+    /// How many candidate answers were left when the last guess was picked, if the guesser
+    /// keeps track.
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Picks the same word `guess` would, and says why: the `top` candidates it weighed and how
+    /// its pick splits the remaining words. Guessers that can't explain themselves return `None`
+    /// without guessing, so `guess` still has to be called.
+    fn explain(&mut self, _history: &[Guess<N>], _top: usize) -> Option<Explanation<N>> {
+        None
+    }
 }
-pub struct Wordle{
-    dictionary: HashSet<&'static Word>
+pub struct Wordle<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    hard_mode: bool,
 }
 
-impl Wordle {
+#[allow(clippy::new_without_default)]
+impl Wordle<'static> {
+    /// A game that accepts any word in the embedded `dictionary.txt`.
     pub fn new()-> Self{
+        Self::with_dictionary(Dictionary::embedded())
+    }
+
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self::with_dictionary(Dictionary::try_embedded()?))
+    }
+}
+
+impl<'d, const N: usize> Wordle<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
         Self {
-            dictionary:HashSet::from_iter(
-                DICT.lines().map(
-                    |line| 
-                    line
-                    .split_once(" ")
-                    .expect("every line is word + space + occurance")
-                    .0
-                    .as_bytes()
-                    .try_into()
-                    .expect("Every word should be 5 characters")
-                ) )       
-            }
+            dictionary,
+            hard_mode: false,
+        }
+    }
+
+    /// In hard mode, every guess must keep revealed greens in place and reuse revealed yellows.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    pub fn contains(&self, word: &Word<N>) -> bool {
+        self.dictionary.contains(word)
     }
-    pub fn play<G: Guesser>(&self, answer: Word, mut guesser: G) -> Option<usize> {
+
+    /// Like `try_play`, but panics on an invalid guess.
+    pub fn play<G: Guesser<N>>(&self, answer: Word<N>, guesser: G) -> GameResult<N> {
+        self.try_play(answer, guesser).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_play<G: Guesser<N>>(&self, answer: Word<N>, mut guesser: G) -> Result<GameResult<N>, Error> {
         // play rounds where it invoke guesser each time
         let mut history = Vec::new();
-        for i in 1..=7 {
-            let guess = guesser.guess(&history); // why the [..]?
-            if guess == answer {return Some(i)}
-            assert!(self.dictionary.contains(&guess), 
-                    "guess '{}' is not in dictionary", 
-                    std::str::from_utf8(&guess).unwrap());
-            let correctness = Correctness::compute(&answer, &guess);
+        let mut turns = Vec::new();
+        while turns.len() < MAX_GUESSES {
+            let start = Instant::now();
+            let guess = guesser.try_guess(&history)?;
+            let elapsed = start.elapsed();
+            let correctness = self.try_guess(&answer, &history, guess)?;
+            turns.push(Turn {
+                word: guess,
+                mask: correctness,
+                remaining: guesser.remaining(),
+                elapsed,
+            });
+            if guess == answer {
+                return Ok(GameResult { answer, turns, won: true });
+            }
             history.push(Guess {
                     word: Cow::Owned(guess),
                     mask: correctness
                 });
         }
-        None
+        Ok(GameResult { answer, turns, won: false })
+    }
+
+    /// Scores a single `guess`, after checking it's allowed to be played on top of `history`.
+    pub fn try_guess(&self, answer: &Word<N>, history: &[Guess<N>], guess: Word<N>) -> Result<[Correctness; N], Error> {
+        if history.len() >= MAX_GUESSES {
+            return Err(Error::TooManyGuesses { limit: MAX_GUESSES });
+        }
+        if guess == *answer {
+            return Ok([Correctness::Correct; N]);
+        }
+        if !self.dictionary.contains(&guess) {
+            return Err(Error::InvalidGuess(String::from_utf8_lossy(&guess).into_owned()));
+        }
+        if self.hard_mode {
+            check_hard_mode(history, &guess)?;
+        }
+        Ok(Correctness::compute(answer, &guess))
     }
 }
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Correctness {
     ///Green
@@ -54,11 +183,9 @@ pub enum Correctness {
     Wrong,
 }
 impl Correctness {
-    fn compute(answer: &Word, guess: &Word) -> [Self; 5] {
-        assert_eq!(answer.len(), 5);
-        assert_eq!(guess.len(), 5);
-        let mut c = [Correctness::Wrong;5];
-        let mut used = [false; 5];
+    fn compute<const N: usize>(answer: &Word<N>, guess: &Word<N>) -> [Self; N] {
+        let mut c = [Correctness::Wrong;N];
+        let mut used = [false; N];
         // Mark things Green
         for (i,(a,g)) in answer.iter().zip(guess.iter()).enumerate() {
             if a ==g {
@@ -85,51 +212,31 @@ impl Correctness {
         c
     }    
     
-    pub fn patterns()-> impl Iterator<Item = [Self; 5]>{
-        itertools::iproduct!(
-            [Self::Correct, Self::Misplaced, Self::Wrong],
-            [Self::Correct, Self::Misplaced, Self::Wrong],
-            [Self::Correct, Self::Misplaced, Self::Wrong],
-            [Self::Correct, Self::Misplaced, Self::Wrong],
-            [Self::Correct, Self::Misplaced, Self::Wrong]
-        ).map(|(a,b,c,d,e)|[a,b,c,d,e])
+    /// All 3^N masks, first position varying slowest.
+    pub fn patterns<const N: usize>()-> impl Iterator<Item = [Self; N]>{
+        Pattern::<N>::all().map(Pattern::mask)
     }
 }
 
 
-pub struct Guess<'a> {
-    pub word: Cow<'a, Word>,
-    pub mask: [Correctness; 5],
+/// A guessed word and the feedback it got, either as a `[Correctness; N]` mask or as a `Pattern`.
+pub struct Guess<'a, const N: usize = 5, M = [Correctness; N]> {
+    pub word: Cow<'a, Word<N>>,
+    pub mask: M,
 }
 
-impl Guess<'_> {
-    pub fn matches(&self, word: &Word) -> bool {
+impl<const N: usize, M: Copy + Into<Pattern<N>>> Guess<'_, N, M> {
+    pub fn pattern(&self) -> Pattern<N> {
+        self.mask.into()
+    }
+
+    pub fn matches(&self, word: &Word<N>) -> bool {
         // if guess G gives mask C against answer A, then
         // guess A should also give mask C against answer G
-        return Correctness::compute(word, &self.word) == self.mask;
+        Pattern::compute(word, &self.word) == self.pattern()
     }
 }
 #[cfg(test)]
-macro_rules! guesser {
-    (|$history: ident| $impl: block) => {{
-        struct G;
-        impl crate::Guesser for G {
-            fn guess(&mut self, $history: &[Guess]) -> $crate::Word {
-                $impl
-            }
-        }
-        G        
-    }};
-}
-
-#[cfg(test)]
-macro_rules! mask {
-    (M) => {crate::Correctness::Misplaced};
-    (C) => {crate::Correctness::Correct};
-    (W) => {crate::Correctness::Wrong};
-    ($($c:tt)+) => {[$(mask!($c)),+]};
-}
-#[cfg(test)]
 mod tests {
     mod guess_matcher{
         use crate::Guess;
@@ -160,16 +267,23 @@ mod tests {
         fn debug(){
             check!(b"baaaa" + [W C M W W] allows b"aaccc");
         }
+        #[test]
+        fn pattern_mask(){
+            let g = Guess {word: Cow::Borrowed(b"baaaa"), mask: crate::Pattern::from(mask!(W C M W W))};
+            assert!(g.matches(b"aaccc"));
+            assert!(!g.matches(b"caacc"));
+        }
     }
     mod game {
-        use crate::{ Wordle, Guess};
+        use crate::{ Wordle, Guess, Guesser};
+        use std::borrow::Cow;
 
         #[test]
         fn genius() {
             let w= Wordle::new();
             let guesser = guesser!(|_history| {*b"moved"} );
             let tmp = w.play(*b"moved", guesser);
-            assert_eq!(tmp, Some(1));
+            assert_eq!(tmp.guesses(), Some(1));
         }
         #[test]
         fn magnificent() {
@@ -181,14 +295,162 @@ mod tests {
                 return b"wrong".to_owned();
             } );
             let tmp = w.play(*b"right", guesser);
-            assert_eq!(tmp, Some(2));
+            assert_eq!(tmp.guesses(), Some(2));
+        }
+        #[test]
+        fn hard_mode() {
+            let w = Wordle::new().with_hard_mode(true);
+            let guesser = guesser!(|history| {
+                if history.is_empty() {
+                    return *b"tares";
+                }
+                *b"right"
+            });
+            assert_eq!(
+                w.try_play(*b"tonic", guesser),
+                Err(crate::Error::HardMode(crate::HardModeViolation::MissingGreen { position: 0, letter: b't' }))
+            );
+        }
+        #[test]
+        fn hard_mode_guesser() {
+            let w = Wordle::new().with_hard_mode(true);
+            for answer in [b"cigar", b"humph", b"awake"] {
+                assert!(w.try_play(*answer, crate::algorithms::Matrix::new()).is_ok());
+            }
+        }
+        #[test]
+        fn invalid_guess() {
+            let w = Wordle::new();
+            let guesser = guesser!(|_history| {*b"zzzzz"} );
+            assert_eq!(w.try_play(*b"right", guesser), Err(crate::Error::InvalidGuess("zzzzz".to_owned())));
+        }
+        #[test]
+        fn answer_outside_dictionary() {
+            let d = crate::Dictionary::<5>::parse("catch\nhatch\n").unwrap();
+            let w = Wordle::with_dictionary(&d);
+            let no_candidates = Err(crate::Error::NoCandidates);
+            assert_eq!(w.try_play(*b"latch", crate::algorithms::Matrix::with_dictionary(&d)), no_candidates);
+            assert_eq!(w.try_play(*b"latch", crate::algorithms::Lookahead::with_dictionary(&d)), no_candidates);
+            assert_eq!(w.try_play(*b"latch", crate::algorithms::Minimax::with_dictionary(&d)), no_candidates);
+            assert_eq!(w.try_play(*b"latch", crate::algorithms::Constrained::with_dictionary(&d)), no_candidates);
+            let host = crate::MultiWordle::with_dictionary(&d);
+            let answers = [*b"catch", *b"latch"];
+            assert_eq!(host.try_play(&answers, crate::algorithms::MultiEntropy::with_dictionary(&d)), Err(crate::Error::NoCandidates));
+        }
+        #[test]
+        fn transcript() {
+            let w = Wordle::new();
+            let guesser = guesser!(|history| {
+                if history.is_empty() {
+                    return *b"wrong";
+                }
+                *b"right"
+            } );
+            let result = w.try_play(*b"right", guesser).unwrap();
+            assert!(result.won);
+            assert_eq!(result.guesses(), Some(2));
+            assert_eq!(result.turns[0].mask, crate::Correctness::compute(b"right", b"wrong"));
+            assert_eq!(result.turns[1].word, *b"right");
+            assert_eq!(result.turns[1].remaining, None);
+        }
+        #[test]
+        fn remaining() {
+            let w = Wordle::new();
+            let result = w.play(*b"cigar", crate::algorithms::Matrix::new());
+            let remaining: Vec<_> = result.turns.iter().map(|turn| turn.remaining.unwrap()).collect();
+            assert_eq!(remaining[0], crate::Dictionary::embedded().len());
+            assert!(remaining.windows(2).all(|w| w[0] > w[1]));
+        }
+        #[test]
+        fn lookahead() {
+            let w = Wordle::new();
+            for answer in [*b"cigar", *b"rebut", *b"sissy", *b"humph"] {
+                assert!(w.play(answer, crate::algorithms::Lookahead::new()).guesses().unwrap() <= 6);
+                assert!(w.play(answer, crate::algorithms::Lookahead::new().with_beam(1)).won);
+            }
+        }
+        #[test]
+        fn lookahead_traps() {
+            // the beam's favourite second guesses leave a family of words like -o-er or -atch
+            // that takes a guess per word, which Matrix steers clear of
+            let w = Wordle::new();
+            for answer in [*b"goner", *b"hatch"] {
+                assert!(w.play(answer, crate::algorithms::Lookahead::new()).won);
+            }
+        }
+        #[test]
+        fn probes() {
+            // none of the -atch words tells the others apart, but a probe for c, w, m and l does
+            let d = crate::Dictionary::<5>::parse("catch\nhatch\nlatch\nmatch\nwatch\ncwmlh\n").unwrap();
+            let history = [Guess { word: Cow::Borrowed(b"batch"), mask: mask![W C C C C] }];
+            let mut plain = crate::algorithms::Matrix::with_dictionary(&d);
+            assert_ne!(&plain.guess(&history), b"cwmlh");
+            let mut probing = crate::algorithms::Matrix::with_dictionary(&d).with_probes(true);
+            assert_eq!(&probing.guess(&history), b"cwmlh");
+        }
+        #[test]
+        fn openers() {
+            // the precomputed openers only hold for the built-in dictionary weighed by counts
+            assert_eq!(&crate::algorithms::Matrix::new().guess(&[]), b"tares");
+            assert_eq!(&crate::algorithms::Minimax::new().guess(&[]), b"serai");
+            let d = crate::Dictionary::<5>::parse("tares\ncatch\nhatch\nlatch\n").unwrap();
+            assert_ne!(&crate::algorithms::Matrix::with_dictionary(&d).guess(&[]), b"tares");
+            assert_ne!(&crate::algorithms::Once::with_dictionary(&d).guess(&[]), b"tares");
+        }
+        #[test]
+        fn priors() {
+            let w = Wordle::new();
+            let answers = crate::Dictionary::embedded_answers();
+            let prior = crate::prior::Answers::new(answers);
+            let result = w.play(*b"cigar", crate::algorithms::Matrix::with_prior(crate::Dictionary::embedded(), &prior));
+            assert!(result.guesses().unwrap() <= 6);
+        }
+        #[test]
+        fn minimax() {
+            let w = Wordle::new();
+            for answer in [*b"cigar", *b"rebut", *b"sissy"] {
+                assert!(w.play(answer, crate::algorithms::Minimax::new()).won);
+            }
+            // the worst case is what the adversary plays for
+            let answers = crate::Dictionary::embedded_answers();
+            let host = crate::Absurdle::with_dictionary(answers, answers);
+            let minimax = host.play(crate::algorithms::Minimax::with_dictionary(answers)).guesses().unwrap();
+            let matrix = host.play(crate::algorithms::Matrix::with_dictionary(answers)).guesses().unwrap();
+            assert!(minimax <= matrix);
+        }
+        #[test]
+        fn constrained() {
+            // the same search as matrix, only pruned differently
+            let w = Wordle::new().with_hard_mode(true);
+            for answer in [*b"cigar", *b"rebut", *b"sissy", *b"eerie"] {
+                let picks = |result: crate::GameResult| -> Vec<_> {
+                    result.turns.iter().map(|turn| (turn.word, turn.remaining)).collect()
+                };
+                let constrained = w.play(answer, crate::algorithms::Constrained::new());
+                assert!(constrained.won);
+                assert_eq!(picks(constrained), picks(w.play(answer, crate::algorithms::Matrix::new())));
+            }
+        }
+        #[test]
+        fn six_letters() {
+            let d = crate::Dictionary::<6>::parse("abacus\nbanana\ncanals\nsalsas\nzebras\nbazaar\n").unwrap();
+            let w = Wordle::with_dictionary(&d);
+            for (answer, _) in d.words() {
+                assert!(w.play(*answer, crate::algorithms::Matrix::with_dictionary(&d)).won);
+                assert!(w.play(*answer, crate::algorithms::Naive::with_dictionary(&d)).won);
+                assert!(w.play(*answer, crate::algorithms::Lookahead::with_dictionary(&d)).won);
+                assert!(w.play(*answer, crate::algorithms::Minimax::with_dictionary(&d)).won);
+                assert!(w.play(*answer, crate::algorithms::Constrained::with_dictionary(&d)).won);
+            }
         }
         #[test]
         fn oops() {
             let w = Wordle::new();
             let guesser = guesser!(|history| {return *b"wrong";} );
             let tmp = w.play(*b"right", guesser);
-            assert_eq!(tmp, None);
+            assert_eq!(tmp.guesses(), None);
+            assert!(!tmp.won);
+            assert_eq!(tmp.turns.len(), crate::MAX_GUESSES);
         }
     }
     mod compute {
@@ -257,6 +519,15 @@ mod tests {
         fn chat3() {
             assert_eq!(Correctness::compute(b"abcde", b"aacde"),mask!(C W C C C))
         }
+        #[test]
+        fn four_letters() {
+            assert_eq!(Correctness::compute(b"abba", b"baab"),mask!(M M M M));
+            assert_eq!(Correctness::compute(b"abba", b"aaaa"),mask!(C W W C));
+        }
+        #[test]
+        fn eight_letters() {
+            assert_eq!(Correctness::compute(b"abcdefgh", b"abcdefhz"),mask!(C C C C C C M W))
+        }
 
     }
 }
\ No newline at end of file
@@ -1,17 +1,151 @@
 // use std::str::FromStr;
-use clap::{Parser, clap_derive::ArgEnum};
-use roget::{Wordle, Guesser};
-const GAMES: &str = include_str!("../answers.txt");
+use clap::{Parser, Subcommand, clap_derive::ArgEnum};
+use roget::{daily, prior::{self, Prior}, Absurdle, DecisionTree, MultiWordle, Word, Dictionary, GameResult, Wordle, Guesser, Guess, Pattern, Correctness, Share, Solver, Summary};
+use std::{borrow::Cow, io::{self, BufRead, Write}, path::{Path, PathBuf}, time::{Instant, SystemTime, UNIX_EPOCH}};
 
 #[derive(Parser, Debug)]
 #[allow(non_snake_case)]
 struct Args {
-   /// Name of the person to greet
-   #[clap(short, long, arg_enum)]
+   /// Which guesser to use
+   #[clap(short, long, arg_enum, default_value = "matrix")]
    Implementation: Implementation,
    
+   /// Only simulate the first `max` answers
    #[clap(short, long)]
-    max: usize,
+    max: Option<usize>,
+
+    /// Enforce hard mode: revealed greens stay in place and revealed yellows must be reused
+    #[clap(long)]
+    hard: bool,
+
+    /// Allowed guesses, as `word count` or one word per line (defaults to the built-in list)
+    #[clap(long)]
+    dictionary: Option<PathBuf>,
+
+    /// Answers to simulate, one word per line (defaults to the built-in list)
+    #[clap(long)]
+    answers: Option<PathBuf>,
+
+    /// Letters per word (4 to 8); anything but 5 needs --dictionary and --answers
+    #[clap(long, default_value_t = 5)]
+    length: usize,
+
+    /// Number of threads to spread simulated games over
+    #[clap(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Candidates the lookahead guesser explores on each move
+    #[clap(long, default_value_t = roget::algorithms::Lookahead::<5>::DEFAULT_BEAM)]
+    beam: usize,
+
+    /// Let the matrix guesser pick words that can't be the answer when they narrow things down
+    /// better (not allowed with --hard)
+    #[clap(long)]
+    probe: bool,
+
+    /// How likely each dictionary word is taken to be the answer
+    #[clap(long, arg_enum, default_value = "counts")]
+    prior: PriorModel,
+
+    /// Print why the guesser picks each word, to stderr (not for the tree guesser)
+    #[clap(long)]
+    explain: bool,
+
+    /// Strategy for the tree guesser, as written by the tree subcommand
+    #[clap(long)]
+    tree: Option<PathBuf>,
+
+    /// How to report simulated games: json writes one object per line with the summary last,
+    /// csv writes one row per game and the summary to stderr, share writes each game as the emoji
+    /// grid players post, numbered by its place in the answer list
+    #[clap(short, long, arg_enum, default_value = "text")]
+    format: Format,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Help solve a real game: suggests a word, then reads back the colours you got
+    Solve,
+    /// Play a game yourself; type `hint` at the prompt for the guesser's suggestion
+    Play {
+        /// Pick the answer from this seed instead of at random
+        #[clap(long)]
+        seed: Option<u64>,
+
+        /// Play the puzzle of this day (YYYY-MM-DD)
+        #[clap(long, conflicts_with_all = &["seed", "today"])]
+        date: Option<String>,
+
+        /// Play today's puzzle
+        #[clap(long, conflicts_with = "seed")]
+        today: bool,
+    },
+    /// Read a shared emoji grid from stdin and list the guesses most likely to have made it
+    Reverse {
+        /// The answer of the game; otherwise the header's puzzle number picks it from the answer
+        /// list, and without one every answer is tried
+        #[clap(long)]
+        answer: Option<String>,
+
+        /// How many guess sequences to list
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Grade a finished game guess by guess against what the matrix, lookahead and minimax
+    /// guessers would have played
+    Review {
+        answer: String,
+
+        /// The words played, in order
+        #[clap(required = true)]
+        words: Vec<String>,
+    },
+    /// Play against a host that dodges every guess for as long as it can
+    Absurdle,
+    /// Simulate Dordle-style games, taking the answers a group of boards at a time
+    Multi {
+        /// Boards per game: 2 for Dordle, 4 for Quordle, 8 for Octordle
+        #[clap(long, default_value_t = 4)]
+        boards: usize,
+    },
+    /// Search for the strategy tree that solves the answers in the fewest guesses, written as
+    /// one line per answer, or as JSON with --format json
+    Tree {
+        /// Where to write the tree (defaults to stdout)
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+
+        /// Only try this many guesses per node; faster, but no longer provably optimal
+        #[clap(long)]
+        candidates: Option<usize>,
+
+        /// Most guesses any answer may take
+        #[clap(long, default_value_t = 6)]
+        max_depth: usize,
+    },
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+    Share,
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy)]
+enum PriorModel {
+    /// The counts in the dictionary
+    Counts,
+    /// Every word equally
+    Uniform,
+    /// Common words by count rank, falling off after the first few thousand
+    Sigmoid,
+    /// Only words in the answer list
+    Answers,
 }
 
 #[derive(ArgEnum, Debug, Clone, Copy)]
@@ -19,35 +153,632 @@ enum Implementation {
     Naive,
     Allocs,
     VecRem,
-    Once
+    Once,
+    Matrix,
+    Lookahead,
+    Minimax,
+    Constrained,
+    Tree,
 }
 
 fn main() {
     let args = Args::parse();
-    
+    if args.probe && args.hard {
+        eprintln!("probe guesses break the hard mode rules, pick one of --probe and --hard");
+        std::process::exit(1);
+    }
+    if args.explain {
+        if matches!(args.Implementation, Implementation::Tree) {
+            eprintln!("--explain doesn't apply to the tree guesser, which only follows its strategy");
+            std::process::exit(1);
+        }
+        if !matches!(args.command, None | Some(Command::Solve | Command::Play { .. } | Command::Absurdle)) {
+            eprintln!("--explain only applies to games a guesser plays: simulations, solve, play and absurdle");
+            std::process::exit(1);
+        }
+    }
+
+    match args.length {
+        4 => with_length::<4>(&args, None, None),
+        5 => with_length(&args, Some(Dictionary::embedded), Some(Dictionary::embedded_answers)),
+        6 => with_length::<6>(&args, None, None),
+        7 => with_length::<7>(&args, None, None),
+        8 => with_length::<8>(&args, None, None),
+        n => {
+            eprintln!("{} letter words are not supported, pick 4 to 8", n);
+            std::process::exit(1);
+        }
+    }
+}
+
+type Embedded<const N: usize> = Option<fn() -> &'static Dictionary<N>>;
+
+fn with_length<const N: usize>(args: &Args, embedded: Embedded<N>, embedded_answers: Embedded<N>) {
+    let dictionary = load(&args.dictionary, embedded, "--dictionary");
+    let answers = load(&args.answers, embedded_answers, "--answers");
+    // the guessers only ever look for the answer among the dictionary words
+    if let Some((answer, _)) = answers.words().iter().find(|(answer, _)| !dictionary.contains(answer)) {
+        eprintln!("answer '{}' is not in the dictionary", String::from_utf8_lossy(answer));
+        std::process::exit(1);
+    }
+    if let Some(Command::Tree { out, candidates, max_depth }) = &args.command {
+        return tree(dictionary, answers, args.max, out.as_deref(), *candidates, *max_depth, args.format);
+    }
+    if let Some(Command::Reverse { answer, top }) = &args.command {
+        return reverse(dictionary, answers, answer.as_deref(), *top, args.format);
+    }
+
+    // worked out once and shared, rather than once per game
+    let prior = match args.prior {
+        PriorModel::Counts => prior::Counts.weights(dictionary),
+        PriorModel::Uniform => prior::Uniform.weights(dictionary),
+        PriorModel::Sigmoid => prior::Sigmoid::default().weights(dictionary),
+        PriorModel::Answers => prior::Answers::new(answers).weights(dictionary),
+    };
+    let prior = &prior;
+    if let Some(Command::Review { answer, words }) = &args.command {
+        return review(dictionary, prior, answer, words, args);
+    }
+    if let Some(Command::Multi { boards }) = &args.command {
+        return multi(dictionary, answers, prior, *boards, args);
+    }
 
     match args.Implementation {
-        Implementation::Naive => play(roget::algorithms::Naive::new, Some(args.max)),
-        Implementation::Allocs=> play(roget::algorithms::Allocs::new, Some(args.max)),
-        Implementation::VecRem => play(roget::algorithms::VecRem::new, Some(args.max)),
-        Implementation::Once => play(roget::algorithms::Once::new, Some(args.max)),
+        Implementation::Naive => run(|| roget::algorithms::Naive::with_prior(dictionary, prior), args, dictionary, answers),
+        Implementation::Allocs=> run(|| roget::algorithms::Allocs::with_prior(dictionary, prior), args, dictionary, answers),
+        Implementation::VecRem => run(|| roget::algorithms::VecRem::with_prior(dictionary, prior), args, dictionary, answers),
+        Implementation::Once => run(|| roget::algorithms::Once::with_prior(dictionary, prior), args, dictionary, answers),
+        Implementation::Matrix => run(
+            || roget::algorithms::Matrix::with_prior(dictionary, prior).with_probes(args.probe),
+            args,
+            dictionary,
+            answers,
+        ),
+        Implementation::Lookahead => run(
+            || roget::algorithms::Lookahead::with_prior(dictionary, prior).with_beam(args.beam),
+            args,
+            dictionary,
+            answers,
+        ),
+        Implementation::Minimax => run(|| roget::algorithms::Minimax::with_prior(dictionary, prior), args, dictionary, answers),
+        Implementation::Constrained => run(|| roget::algorithms::Constrained::with_prior(dictionary, prior), args, dictionary, answers),
+        Implementation::Tree => {
+            let tree = load_tree(args, dictionary, answers);
+            run(|| roget::TreeGuesser::new(&tree), args, dictionary, answers)
+        }
+    }
+}
+
+/// Reads the strategy for `-i tree` and makes sure it can play every answer that will come up.
+fn load_tree<const N: usize>(args: &Args, dictionary: &Dictionary<N>, answers: &Dictionary<N>) -> DecisionTree<N> {
+    let Some(path) = &args.tree else {
+        eprintln!("the tree guesser needs --tree");
+        std::process::exit(1);
+    };
+    let answers: Vec<_> = answers.words().iter().take(args.max.unwrap_or(usize::MAX)).map(|(answer, _)| *answer).collect();
+    match DecisionTree::from_file(path).and_then(|tree| tree.validate(dictionary, &answers).map(|()| tree)) {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Reads the word list at `path`, or falls back to the built-in one. Loaded lists live for the
+/// rest of the process, just like the built-in ones.
+fn load<const N: usize>(path: &Option<PathBuf>, embedded: Embedded<N>, flag: &str) -> &'static Dictionary<N> {
+    let Some(path) = path else {
+        if let Some(embedded) = embedded {
+            return embedded();
+        }
+        eprintln!("the built-in word lists only have 5 letter words, pass {} for {} letters", flag, N);
+        std::process::exit(1);
+    };
+    match Dictionary::from_file(path) {
+        Ok(dictionary) => Box::leak(Box::new(dictionary)),
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run<G, const N: usize>(mk: impl Fn()->G + Sync, args: &Args, dictionary: &Dictionary<N>, answers: &Dictionary<N>) where G: Guesser<N> + Send {
+    if args.explain {
+        run_with(|| Explained(mk()), args, dictionary, answers)
+    } else {
+        run_with(mk, args, dictionary, answers)
+    }
+}
+
+/// How many candidates `--explain` lists.
+const EXPLAIN_TOP: usize = 5;
+
+/// Prints why the guesser picks every word, to stderr.
+struct Explained<G>(G);
+
+impl<G> Explained<G> {
+    fn explain<const N: usize>(&mut self, history: &[Guess<N>]) -> Option<Word<N>> where G: Guesser<N> {
+        let explanation = self.0.explain(history, EXPLAIN_TOP)?;
+        eprintln!("{}", explanation);
+        Some(explanation.chosen)
+    }
+}
+
+impl<G: Guesser<N>, const N: usize> Guesser<N> for Explained<G> {
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.explain(history).unwrap_or_else(|| self.0.guess(history))
+    }
+
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, roget::Error> {
+        match self.explain(history) {
+            Some(guess) => Ok(guess),
+            None => self.0.try_guess(history),
+        }
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.0.remaining()
+    }
+}
+
+fn run_with<G, const N: usize>(mk: impl Fn()->G + Sync, args: &Args, dictionary: &Dictionary<N>, answers: &Dictionary<N>) where G: Guesser<N> + Send {
+    let w = Wordle::with_dictionary(dictionary).with_hard_mode(args.hard);
+    match args.command {
+        None => play(mk, &w, answers, args.max, args.jobs, args.hard, args.format),
+        Some(Command::Solve) => solve((mk)(), &w, dictionary, args.hard),
+        Some(Command::Play { seed, ref date, today }) => {
+            let answer = pick_answer(answers, seed, date.as_deref(), today);
+            play_human(mk, &w, dictionary, answer)
+        }
+        Some(Command::Absurdle) => absurdle(mk(), dictionary, answers, args.hard),
+        Some(Command::Tree { .. } | Command::Multi { .. } | Command::Reverse { .. } | Command::Review { .. }) => unreachable!("handled before picking a guesser"),
+    }
+}
+
+fn multi<const N: usize>(dictionary: &Dictionary<N>, answers: &Dictionary<N>, prior: &Vec<f64>, boards: usize, args: &Args) {
+    // multi-board games have their own guesser, and a simpler report
+    let unsupported = [
+        (!matches!(args.Implementation, Implementation::Matrix), "-i"),
+        (args.hard, "--hard"),
+        (args.jobs != 1, "--jobs"),
+        (args.format != Format::Text, "--format"),
+        (args.probe, "--probe"),
+    ];
+    if let Some((_, flag)) = unsupported.iter().find(|(set, _)| *set) {
+        eprintln!("{} doesn't apply to multi-board games", flag);
+        std::process::exit(1);
+    }
+    if boards == 0 {
+        eprintln!("--boards must be at least 1");
+        std::process::exit(1);
+    }
+    let answers: Vec<_> = answers.words().iter().take(args.max.unwrap_or(usize::MAX)).map(|(answer, _)| *answer).collect();
+    let host = MultiWordle::with_dictionary(dictionary);
+    let (mut games, mut won, mut guesses) = (0, 0, 0);
+    let groups = answers.chunks_exact(boards);
+    let left_over = groups.remainder().len();
+    for group in groups {
+        let result = match host.try_play(group, roget::algorithms::MultiEntropy::with_prior(dictionary, prior)) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("game stopped: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let group: Vec<_> = group.iter().map(|answer| std::str::from_utf8(answer).unwrap()).collect();
+        games += 1;
+        match result.guesses() {
+            Some(n) => {
+                won += 1;
+                guesses += n;
+                println!("Solved {} in {}", group.join(" "), n);
+            }
+            None => {
+                let solved = result.boards.iter().filter(|board| board.won).count();
+                eprintln!("failed {} ({} of {} solved)", group.join(" "), solved, group.len());
+            }
+        }
+    }
+    if left_over != 0 {
+        // a smaller game is easier, so it would skew the mean
+        eprintln!("skipped the last {} answers, too few for a game of {} boards", left_over, boards);
+    }
+    println!("games played: {}", games);
+    println!("won:          {}", won);
+    if won != 0 {
+        println!("mean guesses: {:.3}", guesses as f64 / won as f64);
+    }
+}
+
+/// The answer of a dated puzzle, a seeded one, or a random one.
+fn pick_answer<const N: usize>(answers: &Dictionary<N>, seed: Option<u64>, date: Option<&str>, today: bool) -> Word<N> {
+    let words = answers.words();
+    let index = if today {
+        daily::answer_index(daily::today(), words.len())
+    } else if let Some(date) = date {
+        match daily::parse_date(date) {
+            Some(day) => daily::answer_index(day, words.len()),
+            None => {
+                eprintln!("'{}' is not a YYYY-MM-DD date", date);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        let seed = seed.unwrap_or_else(|| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            now.as_nanos() as u64 ^ u64::from(std::process::id())
+        });
+        daily::seeded_index(seed, words.len())
+    };
+    match index {
+        Ok(index) => words[index].0,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn play_human<G, const N: usize>(mk: impl Fn() -> G, w: &Wordle<N>, dictionary: &Dictionary<N>, answer: Word<N>) where G: Guesser<N> {
+    let mut lines = io::stdin().lock().lines();
+    let limit = roget::MAX_GUESSES;
+    let mut history: Vec<Guess<N>> = Vec::new();
+    let mut guesser = mk();
+    // how much of the history the guesser has been shown
+    let mut seen = 0;
+    println!("Guess the {} letter word in {} tries, or type 'hint' for a suggestion.", N, limit);
+    while history.len() < limit {
+        let Some(line) = prompt(&mut lines, &format!("guess {}/{}: ", history.len() + 1, limit)) else { return };
+        let line = line.to_ascii_lowercase();
+        if line == "hint" {
+            // guessers only look at the newest guess, so catch them up one guess at a time
+            let suggestion = (seen + 1..history.len())
+                .try_for_each(|played| guesser.try_guess(&history[..played]).map(drop))
+                .and_then(|()| guesser.try_guess(&history));
+            seen = history.len();
+            // e.g. a tree guesser once the game leaves its tree, but the game itself goes on
+            let suggestion = match suggestion {
+                Ok(suggestion) => suggestion,
+                Err(e) => {
+                    eprintln!("no hint: {}", e);
+                    continue;
+                }
+            };
+            let remaining = guesser.remaining().unwrap_or_else(|| {
+                dictionary.words().iter().filter(|(word, _)| history.iter().all(|g| g.matches(word))).count()
+            });
+            println!("{} candidates remain, try: {}", remaining, std::str::from_utf8(&suggestion).unwrap());
+            continue;
+        }
+        let Ok(guess) = line.as_bytes().try_into() else {
+            eprintln!("'{}' is not a {} letter word", line, N);
+            continue;
+        };
+        match w.try_guess(&answer, &history, guess) {
+            Ok(mask) => {
+                println!("{}", colour(&guess, &mask));
+                if guess == answer {
+                    println!("Solved in {}/{}", history.len() + 1, limit);
+                    return;
+                }
+                history.push(Guess { word: Cow::Owned(guess), mask });
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    println!("Out of guesses, the word was {}", std::str::from_utf8(&answer).unwrap().to_ascii_uppercase());
+}
 
+/// `word` as letters on green, yellow and gray tiles, using ANSI colours.
+fn colour<const N: usize>(word: &Word<N>, mask: &[Correctness; N]) -> String {
+    let mut out = String::new();
+    for (&letter, c) in word.iter().zip(mask) {
+        let background = match c {
+            Correctness::Correct => "42",
+            Correctness::Misplaced => "43",
+            Correctness::Wrong => "100",
+        };
+        out.push_str(&format!("\x1b[1;97;{}m {} \x1b[0m", background, letter.to_ascii_uppercase() as char));
     }
+    out
+}
 
+fn absurdle<G, const N: usize>(guesser: G, dictionary: &Dictionary<N>, answers: &Dictionary<N>, hard: bool) where G: Guesser<N> {
+    let host = Absurdle::with_dictionary(dictionary, answers).with_hard_mode(hard);
+    let result = match host.try_play(guesser) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("game stopped: {}", e);
+            std::process::exit(1);
+        }
+    };
+    for turn in &result.turns {
+        println!("{} {}", std::str::from_utf8(&turn.word).unwrap(), Pattern::from(turn.mask));
+    }
+    match result.guesses() {
+        Some(n) => println!("Cornered the host on {} in {}", std::str::from_utf8(&result.answer).unwrap(), n),
+        None => println!("Ran out of guesses with {} still open", std::str::from_utf8(&result.answer).unwrap()),
+    }
 }
 
-fn play<G>(mut mk: impl FnMut()->G, max:Option<usize>) where G: Guesser {
-    let w = Wordle::new();
-    for answer in GAMES
-            .split_whitespace()
+fn review<const N: usize>(dictionary: &Dictionary<N>, prior: &Vec<f64>, answer: &str, words: &[String], args: &Args) {
+    let word = |s: &str| -> Word<N> {
+        s.to_ascii_lowercase().as_bytes().try_into().unwrap_or_else(|_| {
+            eprintln!("'{}' is not a {} letter word", s, N);
+            std::process::exit(1);
+        })
+    };
+    let words: Vec<_> = words.iter().map(|s| word(s)).collect();
+    let mut matrix = roget::algorithms::Matrix::with_prior(dictionary, prior);
+    let mut lookahead = roget::algorithms::Lookahead::with_prior(dictionary, prior).with_beam(args.beam);
+    let mut minimax = roget::algorithms::Minimax::with_prior(dictionary, prior);
+    match roget::review(dictionary, prior, word(answer), &words, &mut [&mut matrix, &mut lookahead, &mut minimax]) {
+        Ok(review) => match args.format {
+            Format::Text => println!("{}", review),
+            Format::Json => println!("{}", review.to_json()),
+            Format::Csv | Format::Share => {
+                eprintln!("reviews can be written as text or json");
+                std::process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn reverse<const N: usize>(dictionary: &Dictionary<N>, answers: &Dictionary<N>, answer: Option<&str>, top: usize, format: Format) {
+    let mut grid = String::new();
+    if let Err(e) = io::Read::read_to_string(&mut io::stdin(), &mut grid) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    let share: Share<N> = match grid.parse() {
+        Ok(share) => share,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let answers: Vec<Word<N>> = match (answer, share.puzzle) {
+        (Some(answer), _) => match answer.to_ascii_lowercase().as_bytes().try_into() {
+            Ok(answer) => vec![answer],
+            Err(_) => {
+                eprintln!("'{}' is not a {} letter word", answer, N);
+                std::process::exit(1);
+            }
+        },
+        (None, Some(puzzle)) => {
+            let words = answers.words();
+            match daily::answer_index(i64::from(puzzle), words.len()) {
+                Ok(index) => vec![words[index].0],
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        (None, None) => answers.words().iter().map(|(answer, _)| *answer).collect(),
+    };
+    let found = match roget::reverse::reconstruct(dictionary, &answers, &share, top) {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if found.is_empty() {
+        eprintln!("no dictionary words give that grid");
+        std::process::exit(1);
+    }
+    for reconstruction in found {
+        let answer = std::str::from_utf8(&reconstruction.answer).unwrap();
+        let guesses: Vec<_> = reconstruction.guesses.iter().map(|g| std::str::from_utf8(g).unwrap()).collect();
+        match format {
+            Format::Text => println!("{:8.2}  {}  ({})", reconstruction.score, guesses.join(" "), answer),
+            Format::Json => println!(
+                "{}",
+                serde_json::json!({ "answer": answer, "guesses": guesses, "score": reconstruction.score })
+            ),
+            Format::Csv | Format::Share => {
+                eprintln!("guess sequences can be written as text or json");
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn tree<const N: usize>(
+    dictionary: &Dictionary<N>,
+    answers: &Dictionary<N>,
+    max: Option<usize>,
+    out: Option<&Path>,
+    candidates: Option<usize>,
+    max_depth: usize,
+    format: Format,
+) {
+    let answers: Vec<_> = answers.words().iter().take(max.unwrap_or(usize::MAX)).map(|(answer, _)| *answer).collect();
+    let mut solver = Solver::new(dictionary).with_max_depth(max_depth);
+    if let Some(candidates) = candidates {
+        solver = solver.with_candidates(candidates);
+    }
+    let start = Instant::now();
+    let tree = match solver.solve(&answers) {
+        Ok(Some(tree)) => tree,
+        Ok(None) => {
+            eprintln!("the answers can't all be solved in {} guesses", max_depth);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    eprintln!(
+        "{} answers in {} guesses (mean {:.4}, at most {}), found in {:?}",
+        tree.answers(),
+        tree.total_guesses(),
+        tree.total_guesses() as f64 / tree.answers() as f64,
+        tree.depth(),
+        start.elapsed()
+    );
+    let written = match format {
+        Format::Text => tree.to_text(),
+        Format::Json => tree.to_json().to_string(),
+        Format::Csv | Format::Share => {
+            eprintln!("trees can be written as text or json");
+            std::process::exit(1);
+        }
+    };
+    match out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, written) {
+                eprintln!("{}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", written),
+    }
+}
+
+fn play<G, const N: usize>(mk: impl Fn()->G + Sync, w: &Wordle<N>, answers: &Dictionary<N>, max:Option<usize>, jobs: usize, hard: bool, format: Format) where G: Guesser<N> + Send {
+    let start = Instant::now();
+    let answers: Vec<_> = answers
+            .words()
+            .iter()
             .take(max.unwrap_or(usize::MAX))
-    {
-        let answer_b: roget::Word = answer.as_bytes().try_into().unwrap();
-        let guesser = (mk)();
-        if let Some(score) = w.play(answer_b, guesser){
-            println!("Guessed {} in {}", answer, score);
-        }else{
-            eprintln!("failed to guess ");
+            .map(|(answer, _)| *answer)
+            .collect();
+    if format == Format::Csv {
+        println!("{}", GameResult::<N>::CSV_HEADER);
+    }
+    let mut results = Vec::new();
+    roget::simulate(w, &answers, mk, jobs, |answer, result| {
+        let answer_s = std::str::from_utf8(answer).unwrap();
+        match (&result, format) {
+            (Ok(result), Format::Text) => match result.guesses() {
+                Some(score) => println!("Guessed {} in {}", answer_s, score),
+                None => eprintln!("failed to guess {}", answer_s),
+            },
+            (Ok(result), Format::Json) => println!("{}", result.to_json()),
+            (Ok(result), Format::Csv) => println!("{}", result.to_csv()),
+            (Ok(result), Format::Share) => {
+                let puzzle = answers.iter().position(|a| a == answer).unwrap_or(0);
+                println!("{}\n", Share::from_result(result).with_puzzle(puzzle as u32).with_hard_mode(hard));
+            }
+            (Err(e), Format::Json) => {
+                println!("{}", serde_json::json!({ "answer": answer_s, "outcome": "error", "error": e.to_string() }))
+            }
+            (Err(_), Format::Csv) => println!("{},error,,,,", answer_s),
+            (Err(e), Format::Text | Format::Share) => eprintln!("game for {} stopped: {}", answer_s, e),
+        }
+        if let Ok(result) = result {
+            results.push(result);
+        }
+    });
+    let summary = Summary::from_results(&results).with_wall_time(start.elapsed());
+    match format {
+        Format::Text => println!("{}", summary),
+        Format::Json => println!("{}", serde_json::json!({ "summary": summary.to_json() })),
+        Format::Csv => eprintln!("{}\n{}", Summary::<N>::CSV_HEADER, summary.to_csv()),
+        Format::Share => eprintln!("{}", summary),
+    }
+}
+
+fn solve<G, const N: usize>(mut guesser: G, w: &Wordle<N>, dictionary: &Dictionary<N>, hard: bool) where G: Guesser<N> {
+    let mut lines = io::stdin().lock().lines();
+    let mut history: Vec<Guess<N>> = Vec::new();
+    loop {
+        let remaining = dictionary
+            .words()
+            .iter()
+            .filter(|(word, _)| history.iter().all(|g| g.matches(word)))
+            .count();
+        let suggestion = match guesser.try_guess(&history) {
+            Ok(suggestion) => suggestion,
+            Err(e) => {
+                eprintln!("game stopped: {}", e);
+                std::process::exit(1);
+            }
+        };
+        println!("{} candidates remain, try: {}", remaining, std::str::from_utf8(&suggestion).unwrap());
+
+        let word = loop {
+            let Some(line) = prompt(&mut lines, "word played (enter to accept): ") else { return };
+            if line.is_empty() {
+                break suggestion;
+            }
+            match line.to_ascii_lowercase().as_bytes().try_into() {
+                Ok(word) if !w.contains(&word) => eprintln!("'{}' is not in the dictionary", line),
+                Ok(word) => match roget::check_hard_mode(&history, &word) {
+                    Err(e) if hard => eprintln!("not allowed in hard mode: {}", e),
+                    _ => break word,
+                },
+                Err(_) => eprintln!("'{}' is not a {} letter word", line, N),
+            }
         };
+        let pattern = loop {
+            let Some(line) = prompt(&mut lines, "colours you got (e.g. gy..g): ") else { return };
+            let pattern: Pattern<N> = match line.parse() {
+                Ok(pattern) => pattern,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    continue;
+                }
+            };
+            history.push(Guess { word: Cow::Owned(word), mask: pattern.mask() });
+            match check_consistent(&history, dictionary) {
+                Ok(()) => break pattern,
+                Err(why) => {
+                    history.pop();
+                    eprintln!("that feedback can't be right: {}", why);
+                }
+            }
+        };
+        if pattern.is_solved() {
+            println!("Solved in {}", history.len());
+            return;
+        }
     }
-}
\ No newline at end of file
+}
+
+fn prompt(lines: &mut impl Iterator<Item = io::Result<String>>, msg: &str) -> Option<String> {
+    print!("{}", msg);
+    io::stdout().flush().ok()?;
+    Some(lines.next()?.ok()?.trim().to_owned())
+}
+
+/// Checks the newest entry of `history` against the ones before it, explaining the first
+/// contradiction it finds.
+fn check_consistent<const N: usize>(history: &[Guess<N>], dictionary: &Dictionary<N>) -> Result<(), String> {
+    let (last, earlier) = history.split_last().expect("history has the new entry");
+    for (i, (&c, &m)) in last.word.iter().zip(&last.mask).enumerate() {
+        // yellows are handed out left to right, so a gray can't come before a yellow of the same letter
+        if m == Correctness::Wrong {
+            if let Some(j) = (i + 1..N).find(|&j| last.word[j] == c && last.mask[j] == Correctness::Misplaced) {
+                return Err(format!("'{}' can't be gray at {} and yellow at {}", c as char, i + 1, j + 1));
+            }
+        }
+    }
+    for g in earlier {
+        for i in 0..N {
+            if g.mask[i] != Correctness::Correct {
+                continue;
+            }
+            let known = g.word[i];
+            if last.word[i] == known && last.mask[i] != Correctness::Correct {
+                return Err(format!("position {} is already known to be '{}'", i + 1, known as char));
+            }
+            if last.word[i] != known && last.mask[i] == Correctness::Correct {
+                return Err(format!("position {} was already green as '{}'", i + 1, known as char));
+            }
+        }
+    }
+    if !dictionary.words().iter().any(|(word, _)| history.iter().all(|g| g.matches(word))) {
+        return Err("no dictionary word fits all the feedback so far".to_owned());
+    }
+    Ok(())
+}
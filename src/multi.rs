@@ -0,0 +1,136 @@
+use crate::{Correctness, Dictionary, Error, GameResult, Guess, Turn, Word};
+use std::{borrow::Cow, time::Instant};
+
+/// Picks the next word for a game on several boards at once.
+pub trait MultiGuesser<const N: usize = 5> {
+    /// `boards` has the history of every board. A board's history stops at the guess that
+    /// solved it, see `is_solved`.
+    fn guess(&mut self, boards: &[Vec<Guess<N>>]) -> Word<N>;
+
+    /// Like `guess`, for guessers that can get stuck. `MultiWordle::try_play` reports the error
+    /// instead of panicking.
+    fn try_guess(&mut self, boards: &[Vec<Guess<N>>]) -> Result<Word<N>, Error> {
+        Ok(self.guess(boards))
+    }
+}
+
+/// Whether the board with this history is done.
+pub fn is_solved<const N: usize>(history: &[Guess<N>]) -> bool {
+    history.last().is_some_and(|g| g.mask == [Correctness::Correct; N])
+}
+
+/// Guesses allowed for a game on `boards` boards: Wordle's six, plus one for every extra board.
+/// That's 7 for Dordle, 9 for Quordle and 13 for Octordle.
+pub const fn budget(boards: usize) -> usize {
+    boards + 5
+}
+
+/// Hosts Dordle-style games, where every guess is played on all boards that are still open.
+pub struct MultiWordle<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+}
+
+impl MultiWordle<'static> {
+    /// A game that accepts any word in the embedded `dictionary.txt`.
+    pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::embedded())
+    }
+}
+
+impl<'d, const N: usize> MultiWordle<'d, N> {
+    pub fn with_dictionary(dictionary: &'d Dictionary<N>) -> Self {
+        MultiWordle { dictionary }
+    }
+
+    /// Like `try_play`, but panics on an invalid guess.
+    pub fn play<G: MultiGuesser<N>>(&self, answers: &[Word<N>], guesser: G) -> MultiResult<N> {
+        self.try_play(answers, guesser).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Plays one board per answer, with `budget(answers.len())` guesses for all of them.
+    pub fn try_play<G: MultiGuesser<N>>(&self, answers: &[Word<N>], mut guesser: G) -> Result<MultiResult<N>, Error> {
+        let mut boards: Vec<Vec<Guess<N>>> = answers.iter().map(|_| Vec::new()).collect();
+        let mut turns: Vec<Vec<Turn<N>>> = vec![Vec::new(); answers.len()];
+        let mut guesses = 0;
+        while guesses < budget(answers.len()) && !boards.iter().all(|history| is_solved(history)) {
+            let start = Instant::now();
+            let guess = guesser.try_guess(&boards)?;
+            let elapsed = start.elapsed();
+            if !self.dictionary.contains(&guess) && !answers.contains(&guess) {
+                return Err(Error::InvalidGuess(String::from_utf8_lossy(&guess).into_owned()));
+            }
+            guesses += 1;
+            for ((answer, history), turns) in answers.iter().zip(&mut boards).zip(&mut turns) {
+                if is_solved(history) {
+                    continue;
+                }
+                let mask = Correctness::compute(answer, &guess);
+                turns.push(Turn { word: guess, mask, remaining: None, elapsed });
+                history.push(Guess { word: Cow::Owned(guess), mask });
+            }
+        }
+        let boards = answers
+            .iter()
+            .zip(boards)
+            .zip(turns)
+            .map(|((&answer, history), turns)| GameResult { answer, turns, won: is_solved(&history) })
+            .collect();
+        Ok(MultiResult { boards })
+    }
+}
+
+impl Default for MultiWordle<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How every board of a multi-board game went. A board's turns stop once it's solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiResult<const N: usize = 5> {
+    pub boards: Vec<GameResult<N>>,
+}
+
+impl<const N: usize> MultiResult<N> {
+    pub fn won(&self) -> bool {
+        self.boards.iter().all(|board| board.won)
+    }
+
+    /// How many guesses it took to solve every board, if they all were.
+    pub fn guesses(&self) -> Option<usize> {
+        self.won().then(|| self.boards.iter().map(|board| board.turns.len()).max().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{budget, MultiWordle};
+    use crate::algorithms::MultiEntropy;
+
+    #[test]
+    fn budgets() {
+        assert_eq!([2, 4, 8].map(budget), [7, 9, 13]);
+    }
+
+    #[test]
+    fn quordle() {
+        let host = MultiWordle::new();
+        let answers = [*b"cigar", *b"rebut", *b"sissy", *b"humph"];
+        let result = host.play(&answers, MultiEntropy::new());
+        assert!(result.won());
+        let guesses = result.guesses().unwrap();
+        assert!(guesses <= budget(4));
+        // every board saw the same guesses, up to the one that solved it
+        let longest = result.boards.iter().find(|board| board.turns.len() == guesses).unwrap();
+        for board in &result.boards {
+            assert_eq!(board.turns.last().unwrap().word, board.answer);
+            assert!(board.turns.iter().zip(&longest.turns).all(|(a, b)| a.word == b.word));
+        }
+    }
+
+    #[test]
+    fn same_answer_twice() {
+        let result = MultiWordle::new().play(&[*b"cigar", *b"cigar"], MultiEntropy::new());
+        assert_eq!(result.boards[0].turns, result.boards[1].turns);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{Correctness, Word};
+use std::{fmt, str::FromStr};
+
+/// A whole mask packed into one base-3 digit per position (`Correct` = 0, `Misplaced` = 1,
+/// `Wrong` = 2, first letter most significant), so it fits in a `u16` in `0..3^N` (`0..243` for
+/// 5 letters) and can be used directly as an array index.
+///
+/// Ids are in the same order `Correctness::patterns` yields masks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Pattern<const N: usize = 5>(u16);
+
+impl<const N: usize> Pattern<N> {
+    /// Number of distinct patterns (3^N).
+    pub const COUNT: usize = {
+        assert!(N <= 10, "patterns of more than 10 letters don't fit in a u16");
+        3usize.pow(N as u32)
+    };
+    pub const ALL_CORRECT: Self = Pattern(0);
+    pub const ALL_WRONG: Self = Pattern(Self::COUNT as u16 - 1);
+
+    pub fn new(id: u16) -> Option<Self> {
+        ((id as usize) < Self::COUNT).then_some(Pattern(id))
+    }
+
+    /// For ids already known to be below `COUNT`.
+    pub(crate) fn from_id(id: u16) -> Self {
+        debug_assert!((id as usize) < Self::COUNT);
+        Pattern(id)
+    }
+
+    pub fn id(self) -> u16 {
+        self.0
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT as u16).map(Pattern)
+    }
+
+    pub fn is_solved(self) -> bool {
+        self == Self::ALL_CORRECT
+    }
+
+    /// Same result as `Correctness::compute`, without building the intermediate mask.
+    pub fn compute(answer: &Word<N>, guess: &Word<N>) -> Self {
+        let mut unused = [0u8; 26];
+        for (a, g) in answer.iter().zip(guess) {
+            if a != g {
+                unused[(a - b'a') as usize] += 1;
+            }
+        }
+        let mut id = 0;
+        for (a, g) in answer.iter().zip(guess) {
+            id *= 3;
+            if a == g {
+                continue;
+            }
+            let left = &mut unused[(g - b'a') as usize];
+            if *left > 0 {
+                *left -= 1;
+                id += 1;
+            } else {
+                id += 2;
+            }
+        }
+        Pattern(id)
+    }
+
+    pub fn mask(self) -> [Correctness; N] {
+        let mut mask = [Correctness::Wrong; N];
+        let mut id = self.0;
+        for c in mask.iter_mut().rev() {
+            *c = match id % 3 {
+                0 => Correctness::Correct,
+                1 => Correctness::Misplaced,
+                _ => Correctness::Wrong,
+            };
+            id /= 3;
+        }
+        mask
+    }
+}
+
+impl<const N: usize> From<[Correctness; N]> for Pattern<N> {
+    fn from(mask: [Correctness; N]) -> Self {
+        Pattern(mask.iter().fold(0, |id, c| {
+            id * 3
+                + match c {
+                    Correctness::Correct => 0,
+                    Correctness::Misplaced => 1,
+                    Correctness::Wrong => 2,
+                }
+        }))
+    }
+}
+
+impl<const N: usize> From<Pattern<N>> for [Correctness; N] {
+    fn from(pattern: Pattern<N>) -> Self {
+        pattern.mask()
+    }
+}
+
+/// Renders as emoji squares, or with `{:#}` as letters (`g` green, `y` yellow, `.` gray).
+impl<const N: usize> fmt::Display for Pattern<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.mask() {
+            let s = match (c, f.alternate()) {
+                (Correctness::Correct, false) => "🟩",
+                (Correctness::Misplaced, false) => "🟨",
+                (Correctness::Wrong, false) => "⬛",
+                (Correctness::Correct, true) => "g",
+                (Correctness::Misplaced, true) => "y",
+                (Correctness::Wrong, true) => ".",
+            };
+            f.write_str(s)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePatternError {
+    WrongLength(usize),
+    InvalidChar(char),
+}
+
+impl fmt::Display for ParsePatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength(n) => write!(f, "got {} squares, one per letter is needed", n),
+            Self::InvalidChar(c) => write!(f, "'{}' is not a square (use g/y/. or emoji)", c),
+        }
+    }
+}
+
+impl std::error::Error for ParsePatternError {}
+
+/// Parses the letter form written by `{:#}` (also accepting `-`, `b`, `x` and `w` for gray, in
+/// either case) as well as emoji squares, including the high contrast orange and blue.
+impl<const N: usize> FromStr for Pattern<N> {
+    type Err = ParsePatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mask = s
+            .trim()
+            .chars()
+            // emoji presentation selectors ride along when squares are copied
+            .filter(|&c| c != '\u{fe0f}')
+            .map(|c| match c.to_ascii_lowercase() {
+                'g' | '🟩' | '🟧' => Ok(Correctness::Correct),
+                'y' | '🟨' | '🟦' => Ok(Correctness::Misplaced),
+                '.' | '-' | 'b' | 'x' | 'w' | '⬛' | '⬜' => Ok(Correctness::Wrong),
+                _ => Err(ParsePatternError::InvalidChar(c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let n = mask.len();
+        let mask: [Correctness; N] = mask.try_into().map_err(|_| ParsePatternError::WrongLength(n))?;
+        Ok(mask.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+    use crate::Correctness;
+
+    #[test]
+    fn roundtrip() {
+        for (pattern, mask) in Pattern::<5>::all().zip(Correctness::patterns()) {
+            assert_eq!(Pattern::from(mask), pattern);
+            assert_eq!(pattern.mask(), mask);
+        }
+        assert_eq!(Pattern::<5>::new(243), None);
+        assert_eq!(Pattern::<7>::ALL_WRONG, Pattern::from([Correctness::Wrong; 7]));
+    }
+
+    #[test]
+    fn compute_agrees() {
+        let words: [&crate::Word; 8] = [b"abcde", b"aabbb", b"aaccc", b"ccaac", b"caacc", b"azzaz", b"aaabb", b"baaaa"];
+        for answer in words {
+            for guess in words {
+                assert_eq!(
+                    Pattern::compute(answer, guess).mask(),
+                    Correctness::compute(answer, guess)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn display() {
+        use Correctness::*;
+        let p = Pattern::from([Correct, Misplaced, Wrong, Wrong, Correct]);
+        assert_eq!(p.to_string(), "🟩🟨⬛⬛🟩");
+        assert_eq!(format!("{:#}", p), "gy..g");
+    }
+
+    #[test]
+    fn parse() {
+        let p = Pattern::from([Correctness::Correct, Correctness::Misplaced, Correctness::Wrong, Correctness::Wrong, Correctness::Correct]);
+        assert_eq!("gy..g".parse(), Ok(p));
+        assert_eq!("GYbxG".parse(), Ok(p));
+        assert_eq!("🟩🟨⬛⬜🟩".parse(), Ok(p));
+        assert_eq!("🟧🟦⬛⬛🟧".parse(), Ok(p));
+        assert_eq!("gy.g".parse::<Pattern>(), Err(super::ParsePatternError::WrongLength(4)));
+        assert_eq!("gy.zg".parse::<Pattern>(), Err(super::ParsePatternError::InvalidChar('z')));
+        assert_eq!(
+            "gy.g".parse::<Pattern<4>>().map(Pattern::mask),
+            Ok([Correctness::Correct, Correctness::Misplaced, Correctness::Wrong, Correctness::Correct])
+        );
+    }
+}
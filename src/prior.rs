@@ -0,0 +1,137 @@
+use crate::Dictionary;
+use std::borrow::Cow;
+
+/// How likely each dictionary word is to be the answer, before any guesses are made.
+///
+/// Guessers weigh the remaining words by these when scoring a guess. Only ratios between
+/// weights matter.
+pub trait Prior<const N: usize = 5> {
+    /// One weight per word of `dictionary`, in dictionary order.
+    fn weights(&self, dictionary: &Dictionary<N>) -> Vec<f64>;
+
+    /// Like `weights`, but borrowed when the prior already holds them, so a guesser can share
+    /// them instead of taking a copy per game.
+    fn borrow_weights(&self, dictionary: &Dictionary<N>) -> Cow<'_, [f64]> {
+        Cow::Owned(self.weights(dictionary))
+    }
+}
+
+/// The occurance counts of the word list, as is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Counts;
+
+impl<const N: usize> Prior<N> for Counts {
+    fn weights(&self, dictionary: &Dictionary<N>) -> Vec<f64> {
+        dictionary.words().iter().map(|&(_, count)| count as f64).collect()
+    }
+}
+
+/// Every word is as likely as any other.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Uniform;
+
+impl<const N: usize> Prior<N> for Uniform {
+    fn weights(&self, dictionary: &Dictionary<N>) -> Vec<f64> {
+        vec![1.0; dictionary.len()]
+    }
+}
+
+/// Ranks words by count and squashes the rank through a logistic curve: the `center` most
+/// common words are likely, anything much rarer is not, and `width` sets how sharp the cutoff
+/// is. Unlike raw counts, this doesn't let a handful of very common words drown out the rest.
+#[derive(Debug, Clone, Copy)]
+pub struct Sigmoid {
+    pub center: f64,
+    pub width: f64,
+}
+
+impl Default for Sigmoid {
+    /// Roughly the size of the official answer list.
+    fn default() -> Self {
+        Sigmoid { center: 3000.0, width: 300.0 }
+    }
+}
+
+impl<const N: usize> Prior<N> for Sigmoid {
+    fn weights(&self, dictionary: &Dictionary<N>) -> Vec<f64> {
+        let words = dictionary.words();
+        let mut by_count: Vec<usize> = (0..words.len()).collect();
+        by_count.sort_by(|&a, &b| words[b].1.cmp(&words[a].1));
+        let mut weights = vec![0.0; words.len()];
+        let mut rank = 0;
+        for (i, &word) in by_count.iter().enumerate() {
+            // words with the same count share a rank
+            if i > 0 && words[by_count[i - 1]].1 != words[word].1 {
+                rank = i;
+            }
+            weights[word] = 1.0 / (1.0 + ((rank as f64 - self.center) / self.width).exp());
+        }
+        weights
+    }
+}
+
+/// Words on a known answer list (like `answers.txt`) are equally likely, and everything else
+/// gets the small weight `others`, so a missing answer is unlikely rather than impossible.
+#[derive(Clone, Copy)]
+pub struct Answers<'a, const N: usize = 5> {
+    pub answers: &'a Dictionary<N>,
+    pub others: f64,
+}
+
+impl<'a, const N: usize> Answers<'a, N> {
+    pub fn new(answers: &'a Dictionary<N>) -> Self {
+        Answers { answers, others: 1e-3 }
+    }
+}
+
+impl<const N: usize> Prior<N> for Answers<'_, N> {
+    fn weights(&self, dictionary: &Dictionary<N>) -> Vec<f64> {
+        dictionary
+            .words()
+            .iter()
+            .map(|(word, _)| if self.answers.contains(word) { 1.0 } else { self.others })
+            .collect()
+    }
+}
+
+/// Weights computed up front, so a prior that's expensive to evaluate can be shared by many
+/// games. Must have one weight per dictionary word.
+impl<const N: usize> Prior<N> for Vec<f64> {
+    fn weights(&self, dictionary: &Dictionary<N>) -> Vec<f64> {
+        assert_eq!(self.len(), dictionary.len(), "one weight per dictionary word");
+        self.clone()
+    }
+
+    fn borrow_weights(&self, dictionary: &Dictionary<N>) -> Cow<'_, [f64]> {
+        assert_eq!(self.len(), dictionary.len(), "one weight per dictionary word");
+        Cow::Borrowed(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Counts, Prior, Sigmoid, Uniform};
+    use crate::Dictionary;
+    use std::borrow::Cow;
+
+    #[test]
+    fn priors() {
+        let d = Dictionary::<5>::parse("cigar 50\nrebut 900\naalii 2\nhumph 50\n").unwrap();
+        assert_eq!(Counts.weights(&d), [50.0, 900.0, 2.0, 50.0]);
+        assert_eq!(Uniform.weights(&d), [1.0; 4]);
+
+        let answers = Dictionary::parse("rebut\nhumph\n").unwrap();
+        assert_eq!(Answers::new(&answers).weights(&d), [1e-3, 1.0, 1e-3, 1.0]);
+
+        let sigmoid = Sigmoid { center: 1.0, width: 0.5 }.weights(&d);
+        // rebut ranks 0, cigar and humph share rank 1, aalii ranks 3
+        assert_eq!(sigmoid[0], 0.5);
+        assert_eq!(sigmoid[0], sigmoid[3]);
+        assert!(sigmoid[1] > 0.8 && sigmoid[2] < 0.02);
+
+        // weights worked out up front are lent out rather than copied per game
+        let shared = Counts.weights(&d);
+        assert!(matches!(shared.borrow_weights(&d), Cow::Borrowed(weights) if weights == shared.as_slice()));
+        assert!(matches!(Counts.borrow_weights(&d), Cow::Owned(_)));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{check_hard_mode, Correctness, Dictionary, Error, Guess, Pattern, Share, Word};
+use std::{borrow::Cow, cmp::Ordering, collections::BinaryHeap};
+
+/// A sequence of guesses that gives the rows of a share.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconstruction<const N: usize = 5> {
+    pub answer: Word<N>,
+    /// One word per row.
+    pub guesses: Vec<Word<N>>,
+    /// Sum of the natural logs of the guesses' dictionary counts; higher is more plausible.
+    pub score: f64,
+}
+
+/// For each of `rows`, the dictionary words that get that mask against `answer`, most common
+/// first.
+pub fn row_candidates<const N: usize>(
+    dictionary: &Dictionary<N>,
+    answer: &Word<N>,
+    rows: &[[Correctness; N]],
+) -> Vec<Vec<(Word<N>, usize)>> {
+    let patterns: Vec<Pattern<N>> = rows.iter().map(|&mask| mask.into()).collect();
+    let mut candidates = vec![Vec::new(); rows.len()];
+    for &(word, count) in dictionary.words() {
+        // same as Correctness::compute, without building the mask
+        let pattern = Pattern::compute(answer, &word);
+        for (row, _) in candidates.iter_mut().zip(&patterns).filter(|(_, &p)| p == pattern) {
+            row.push((word, count));
+        }
+    }
+    for row in &mut candidates {
+        row.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    }
+    candidates
+}
+
+/// The `limit` most plausible ways to play `share`, best first, out of every answer in `answers`
+/// that could have given it. No word is guessed twice, and a hard mode share only gets guesses
+/// that follow the hard mode rules. Fails on an answer that isn't a `dictionary` word.
+pub fn reconstruct<const N: usize>(
+    dictionary: &Dictionary<N>,
+    answers: &[Word<N>],
+    share: &Share<N>,
+    limit: usize,
+) -> Result<Vec<Reconstruction<N>>, Error> {
+    // which also keeps letters outside a-z away from Pattern::compute
+    if let Some(answer) = answers.iter().find(|answer| !dictionary.contains(answer)) {
+        return Err(Error::InvalidGuess(String::from_utf8_lossy(answer).into_owned()));
+    }
+    let mut found = Vec::new();
+    for answer in answers {
+        let rows = row_candidates(dictionary, answer, &share.rows);
+        found.extend(best_sequences(answer, &rows, share, limit));
+    }
+    found.sort_by(|a, b| b.score.total_cmp(&a.score));
+    found.truncate(limit);
+    Ok(found)
+}
+
+fn weight(count: usize) -> f64 {
+    (count.max(1) as f64).ln()
+}
+
+/// A prefix of a sequence, given as an index into each row's candidates.
+struct Partial {
+    /// The best score any sequence starting with `choices` can get.
+    bound: f64,
+    choices: Vec<usize>,
+}
+
+impl PartialEq for Partial {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Partial {}
+
+impl PartialOrd for Partial {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Partial {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound.total_cmp(&other.bound)
+    }
+}
+
+/// Best-first search over one answer's row candidates. Popping a prefix pushes its next sibling
+/// in the same row and its first child in the next row, so sequences come out in score order
+/// without expanding whole rows.
+fn best_sequences<const N: usize>(
+    answer: &Word<N>,
+    rows: &[Vec<(Word<N>, usize)>],
+    share: &Share<N>,
+    limit: usize,
+) -> Vec<Reconstruction<N>> {
+    if limit == 0 || rows.is_empty() || rows.iter().any(Vec::is_empty) {
+        return Vec::new();
+    }
+    // the most the rows from each one on can add, taking every row's most common word
+    let mut best_rest = vec![0.0; rows.len() + 1];
+    for r in (0..rows.len()).rev() {
+        best_rest[r] = best_rest[r + 1] + weight(rows[r][0].1);
+    }
+    let score = |choices: &[usize]| -> f64 { choices.iter().zip(rows).map(|(&i, row)| weight(row[i].1)).sum() };
+
+    let mut found = Vec::new();
+    let mut heap = BinaryHeap::from([Partial { bound: best_rest[0], choices: vec![0] }]);
+    while let Some(Partial { choices, .. }) = heap.pop() {
+        let r = choices.len() - 1;
+        if choices[r] + 1 < rows[r].len() {
+            let mut sibling = choices.clone();
+            sibling[r] += 1;
+            heap.push(Partial { bound: score(&sibling) + best_rest[r + 1], choices: sibling });
+        }
+        let guesses: Vec<Word<N>> = choices.iter().zip(rows).map(|(&i, row)| row[i].0).collect();
+        let (guess, earlier) = guesses.split_last().expect("a prefix has a guess");
+        if earlier.contains(guess) {
+            continue;
+        }
+        if share.hard_mode {
+            let history: Vec<Guess<N>> = earlier
+                .iter()
+                .zip(&share.rows)
+                .map(|(word, &mask)| Guess { word: Cow::Borrowed(word), mask })
+                .collect();
+            if check_hard_mode(&history, guess).is_err() {
+                continue;
+            }
+        }
+        if r + 1 == rows.len() {
+            found.push(Reconstruction { answer: *answer, score: score(&choices), guesses });
+            if found.len() == limit {
+                break;
+            }
+        } else {
+            let mut child = choices;
+            child.push(0);
+            heap.push(Partial { bound: score(&child) + best_rest[r + 2], choices: child });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reconstruct, row_candidates};
+    use crate::{Correctness, Dictionary, Error, Share};
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse("cigar 50\nrebut 40\nsissy 30\nhumph 20\nawake 10\nblush 5\nfocal 3\nevade 2\n").unwrap()
+    }
+
+    #[test]
+    fn candidates() {
+        let d = dictionary();
+        let rows = [Correctness::compute(b"blush", b"sissy"), Correctness::compute(b"blush", b"humph")];
+        let candidates = row_candidates(&d, b"blush", &rows);
+        assert_eq!(candidates[0], vec![(*b"sissy", 30)]);
+        assert_eq!(candidates[1], vec![(*b"humph", 20)]);
+    }
+
+    #[test]
+    fn ranked() {
+        let d = dictionary();
+        let share: Share = "⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩".parse().unwrap();
+        let found = reconstruct(&d, &[*b"blush"], &share, 3).unwrap();
+        // words sharing no letter with blush, most common first
+        let firsts: Vec<_> = found.iter().map(|r| r.guesses[0]).collect();
+        assert_eq!(firsts, vec![*b"cigar", *b"awake", *b"evade"]);
+        assert!(found.iter().all(|r| r.guesses[1] == *b"blush"));
+        assert!(found.windows(2).all(|w| w[0].score >= w[1].score));
+
+        // an unsolved grid can come from any answer that explains it
+        let share: Share = "⬛⬛⬛⬛⬛".parse().unwrap();
+        let found = reconstruct(&d, &[*b"blush", *b"evade"], &share, 100).unwrap();
+        assert_eq!(found.iter().filter(|r| r.answer == *b"evade").count(), 3);
+        assert_eq!(found.len(), 6);
+        assert_eq!(reconstruct(&d, &[*b"blush", *b"evade"], &share, 2).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn no_repeats_or_hard_mode_breaks() {
+        let d = dictionary();
+        let share: Share = "⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩".parse().unwrap();
+        let found = reconstruct(&d, &[*b"blush"], &share, 100).unwrap();
+        assert_eq!(found.len(), 6);
+        assert!(found.iter().all(|r| r.guesses[0] != r.guesses[1]));
+
+        let rows = vec![Correctness::compute(b"blush", b"humph"), [Correctness::Wrong; 5], [Correctness::Correct; 5]];
+        let share = Share { rows, ..share };
+        assert_eq!(reconstruct(&d, &[*b"blush"], &share, 10).unwrap().len(), 3);
+        // none of the all gray words keep humph's h and u
+        assert!(reconstruct(&d, &[*b"blush"], &share.with_hard_mode(true), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn answer_outside_dictionary() {
+        let d = dictionary();
+        let share: Share = "🟩🟩🟩🟩🟩".parse().unwrap();
+        assert_eq!(reconstruct(&d, &[*b"ab1de"], &share, 10), Err(Error::InvalidGuess("ab1de".into())));
+        assert_eq!(reconstruct(&d, &[*b"blush", *b"tares"], &share, 10), Err(Error::InvalidGuess("tares".into())));
+    }
+}
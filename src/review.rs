@@ -0,0 +1,262 @@
+use crate::{algorithms::{entropy, retain_consistent}, Correctness, Dictionary, Error, Guess, Guesser, Pattern, Word};
+use serde_json::{json, Value};
+use std::{borrow::Cow, fmt};
+
+/// How one guess of a game went, next to what the guessers would have played instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<const N: usize = 5> {
+    pub word: Word<N>,
+    pub mask: [Correctness; N],
+    /// Candidates still consistent with the game before this guess.
+    pub remaining: usize,
+    /// Bits the guess was expected to gain, i.e. the entropy of its patterns over the candidates.
+    pub expected: f64,
+    /// The suggestion with the most expected bits out of all the guessers.
+    pub best: Word<N>,
+    pub best_expected: f64,
+    /// Bits the guess did gain, from how much of the candidates' weight its mask ruled out, but
+    /// never more than pinning the answer down out of all `remaining` candidates is worth.
+    pub actual: f64,
+    /// Why guessers that had no suggestion here failed; they're left out of `best`.
+    pub failures: Vec<Error>,
+}
+
+impl<const N: usize> Step<N> {
+    /// Bits gained beyond what was expected; negative when the mask was worse than average.
+    pub fn luck(&self) -> f64 {
+        self.actual - self.expected
+    }
+}
+
+/// A graded game, see `review`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Review<const N: usize = 5> {
+    pub answer: Word<N>,
+    /// One per guess, up to the one that found the answer.
+    pub steps: Vec<Step<N>>,
+}
+
+impl<const N: usize> Review<N> {
+    pub fn solved(&self) -> bool {
+        self.steps.last().is_some_and(|step| step.word == self.answer)
+    }
+
+    /// Luck over the whole game.
+    pub fn luck(&self) -> f64 {
+        self.steps.iter().map(Step::luck).sum()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let steps: Vec<_> = self
+            .steps
+            .iter()
+            .map(|step| {
+                json!({
+                    "word": String::from_utf8_lossy(&step.word),
+                    "mask": format!("{:#}", Pattern::from(step.mask)),
+                    "remaining": step.remaining,
+                    "expected_bits": step.expected,
+                    "best": String::from_utf8_lossy(&step.best),
+                    "best_expected_bits": step.best_expected,
+                    "actual_bits": step.actual,
+                    "luck": step.luck(),
+                    "failures": step.failures.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        json!({
+            "answer": String::from_utf8_lossy(&self.answer),
+            "solved": self.solved(),
+            "steps": steps,
+            "luck": self.luck(),
+        })
+    }
+}
+
+/// One line per guess, then the overall luck.
+impl<const N: usize> fmt::Display for Review<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            let best = if step.best == step.word {
+                "best".to_string()
+            } else {
+                format!("best {} {:.2}", String::from_utf8_lossy(&step.best), step.best_expected)
+            };
+            writeln!(
+                f,
+                "{}. {} {}  {:>5} left  expected {:.2} bits ({}), got {:.2}, luck {:+.2}",
+                i + 1,
+                String::from_utf8_lossy(&step.word),
+                Pattern::from(step.mask),
+                step.remaining,
+                step.expected,
+                best,
+                step.actual,
+                step.luck(),
+            )?;
+            for failure in &step.failures {
+                writeln!(f, "   a guesser had no suggestion: {}", failure)?;
+            }
+        }
+        let verdict = match self.luck() {
+            luck if luck > 0.5 => "lucky",
+            luck if luck < -0.5 => "unlucky",
+            _ => "about as lucky as expected",
+        };
+        match self.solved() {
+            true => write!(f, "solved in {}, {} ({:+.2} bits)", self.steps.len(), verdict, self.luck()),
+            false => write!(f, "not solved, {} ({:+.2} bits)", verdict, self.luck()),
+        }
+    }
+}
+
+/// Grades `words` as played against `answer`, with masks from `Correctness::compute`.
+/// `weights` says how likely each dictionary word is by index, as given by a `Prior`. Every one
+/// of `guessers` is asked for its move at each step, and the one expected to gain the most bits
+/// is the benchmark. Words after the one that found the answer are ignored.
+pub fn review<const N: usize>(
+    dictionary: &Dictionary<N>,
+    weights: &[f64],
+    answer: Word<N>,
+    words: &[Word<N>],
+    guessers: &mut [&mut dyn Guesser<N>],
+) -> Result<Review<N>, Error> {
+    if !dictionary.contains(&answer) {
+        return Err(Error::InvalidGuess(String::from_utf8_lossy(&answer).into_owned()));
+    }
+    let index = |word: &Word<N>| {
+        dictionary
+            .index_of(word)
+            .ok_or_else(|| Error::InvalidGuess(String::from_utf8_lossy(word).into_owned()))
+    };
+    let mut remaining: Vec<usize> = (0..dictionary.len()).collect();
+    let mut in_pattern_total = vec![0.0; Pattern::<N>::COUNT];
+    let mut history: Vec<Guess<N>> = Vec::new();
+    let mut steps = Vec::new();
+    for &word in words {
+        let played = index(&word)?;
+        let before = weight(weights, &remaining);
+        let mut bits = |guess| entropy(dictionary.row(guess), &remaining, weights, before, &mut in_pattern_total);
+        let mut best: Option<(Word<N>, f64)> = None;
+        let mut failures = Vec::new();
+        for guesser in guessers.iter_mut() {
+            // one guesser giving up shouldn't cost the review of the others
+            let suggestion = match guesser.try_guess(&history).and_then(|suggestion| Ok((suggestion, index(&suggestion)?))) {
+                Ok(suggestion) => suggestion,
+                Err(e) => {
+                    failures.push(e);
+                    continue;
+                }
+            };
+            let suggested = bits(suggestion.1);
+            if best.is_none_or(|(_, best_bits)| suggested > best_bits) {
+                best = Some((suggestion.0, suggested));
+            }
+        }
+        let expected = bits(played);
+        let (best, best_expected) = best.unwrap_or((word, f64::NAN));
+        let count = remaining.len();
+
+        let guess = Guess { word: Cow::Owned(word), mask: Correctness::compute(&answer, &word) };
+        retain_consistent(dictionary, &mut remaining, &guess);
+        let after = weight(weights, &remaining);
+        // a mask the weights made (next to) impossible would be worth (next to) infinite bits
+        let most = (count as f64).log2();
+        let actual = if after > 0.0 { (before / after).log2().min(most) } else { most };
+        steps.push(Step {
+            word,
+            mask: guess.mask,
+            remaining: count,
+            expected,
+            best,
+            best_expected,
+            actual,
+            failures,
+        });
+        if word == answer {
+            break;
+        }
+        history.push(guess);
+    }
+    Ok(Review { answer, steps })
+}
+
+fn weight(weights: &[f64], remaining: &[usize]) -> f64 {
+    remaining.iter().map(|&w| weights[w]).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::review;
+    use crate::{algorithms::Matrix, prior::Uniform, Dictionary, Error, Guess, Guesser, Prior, Word};
+
+    #[test]
+    fn grades() {
+        let d = Dictionary::<5>::parse("catch\nhatch\nlatch\nmatch\nwatch\nbingo\n").unwrap();
+        let weights = Uniform.weights(&d);
+        let mut matrix = Matrix::with_prior(&d, &Uniform);
+        let review = review(&d, &weights, *b"watch", &[*b"catch", *b"bingo", *b"watch", *b"hatch"], &mut [&mut matrix]).unwrap();
+        assert!(review.solved());
+        assert_eq!(review.steps.len(), 3);
+
+        let first = &review.steps[0];
+        assert_eq!(first.remaining, 6);
+        // catch splits the six words 1 + 4 + 1
+        let expected = 2.0 * (1.0 / 6.0) * 6f64.log2() + (4.0 / 6.0) * 1.5f64.log2();
+        assert!((first.expected - expected).abs() < 1e-9);
+        assert!(first.best_expected >= first.expected);
+        assert!((first.actual - 1.5f64.log2()).abs() < 1e-9);
+        assert!(first.luck() < 0.0);
+
+        // bingo can't split the four words left, and learns nothing
+        assert_eq!(review.steps[1].expected, 0.0);
+        assert_eq!(review.steps[1].actual, 0.0);
+        assert!(review.steps[1].best_expected > 0.0);
+        assert_eq!(review.steps[2].remaining, 4);
+        assert_eq!(review.steps[2].actual, 2.0);
+        assert_eq!(review.to_json()["steps"][0]["mask"], ".gggg");
+    }
+
+    #[test]
+    fn unknown_words() {
+        let d = Dictionary::<5>::parse("catch\nhatch\n").unwrap();
+        let weights = vec![1.0; 2];
+        assert_eq!(review(&d, &weights, *b"catch", &[*b"zzzzz"], &mut []), Err(Error::InvalidGuess("zzzzz".into())));
+        assert_eq!(review(&d, &weights, *b"watch", &[*b"catch"], &mut []), Err(Error::InvalidGuess("watch".into())));
+    }
+
+    #[test]
+    fn unlikely_masks() {
+        let d = Dictionary::<5>::parse("catch\nhatch\nbingo\naalii\n").unwrap();
+        // catch singles out aalii, which the weights all but rule out, but that can't be worth
+        // more than picking one of four words
+        for aalii in [0.0, 1e-12] {
+            let weights = vec![1.0, 1.0, 1.0, aalii];
+            let review = review(&d, &weights, *b"aalii", &[*b"catch", *b"aalii"], &mut []).unwrap();
+            assert_eq!(review.steps[0].actual, 2.0);
+            assert!(review.luck().is_finite());
+        }
+    }
+
+    #[test]
+    fn failing_guessers() {
+        struct Stuck;
+        impl Guesser for Stuck {
+            fn guess(&mut self, _: &[Guess]) -> Word {
+                unreachable!("only asked through try_guess")
+            }
+
+            fn try_guess(&mut self, _: &[Guess]) -> Result<Word, Error> {
+                Err(Error::NoCandidates)
+            }
+        }
+        let d = Dictionary::<5>::parse("catch\nhatch\nlatch\nmatch\nwatch\nbingo\n").unwrap();
+        let weights = Uniform.weights(&d);
+        let mut matrix = Matrix::with_prior(&d, &Uniform);
+        let review = review(&d, &weights, *b"watch", &[*b"catch", *b"watch"], &mut [&mut Stuck, &mut matrix]).unwrap();
+        assert!(review.solved());
+        assert!(review.steps.iter().all(|step| step.failures == [Error::NoCandidates]));
+        assert!(review.steps[0].best_expected >= review.steps[0].expected);
+        assert!(review.to_string().contains("no suggestion"));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{multi::budget, Correctness, GameResult, Guess, ParsePatternError, Pattern};
+use std::{fmt, str::FromStr};
+
+/// Which squares a share grid is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// Green and yellow on black.
+    Dark,
+    /// Green and yellow on white.
+    Light,
+    /// Orange and blue for colour blind players, on black.
+    HighContrast,
+}
+
+impl Theme {
+    fn square(self, c: Correctness) -> &'static str {
+        match (self, c) {
+            (Theme::HighContrast, Correctness::Correct) => "🟧",
+            (Theme::HighContrast, Correctness::Misplaced) => "🟦",
+            (_, Correctness::Correct) => "🟩",
+            (_, Correctness::Misplaced) => "🟨",
+            (Theme::Light, Correctness::Wrong) => "⬜",
+            (_, Correctness::Wrong) => "⬛",
+        }
+    }
+}
+
+/// A game as players share it: a header like `Wordle 512 4/6*` and one row of squares per guess,
+/// without the words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share<const N: usize = 5> {
+    /// The puzzle number, if the header has one.
+    pub puzzle: Option<u32>,
+    /// Guesses the game allowed, the number after the `/`.
+    pub max_guesses: usize,
+    /// Marked by a `*` after the score.
+    pub hard_mode: bool,
+    pub rows: Vec<[Correctness; N]>,
+}
+
+impl<const N: usize> Share<N> {
+    /// The masks of `history`, out of the six guesses Wordle allows. A game that needed the
+    /// seventh guess `Wordle::play` gives is scored as lost, `X/6`.
+    pub fn from_history(history: &[Guess<N>]) -> Self {
+        Self::from_masks(history.iter().map(|g| g.mask).collect())
+    }
+
+    pub fn from_result(result: &GameResult<N>) -> Self {
+        Self::from_masks(result.turns.iter().map(|turn| turn.mask).collect())
+    }
+
+    fn from_masks(rows: Vec<[Correctness; N]>) -> Self {
+        Share { puzzle: None, max_guesses: budget(1), hard_mode: false, rows }
+    }
+
+    pub fn with_puzzle(mut self, puzzle: u32) -> Self {
+        self.puzzle = Some(puzzle);
+        self
+    }
+
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// How many guesses it took, if the last row is all green within `max_guesses`.
+    pub fn guesses(&self) -> Option<usize> {
+        let solved = self.rows.last().is_some_and(|mask| *mask == [Correctness::Correct; N]);
+        (solved && self.rows.len() <= self.max_guesses).then_some(self.rows.len())
+    }
+
+    pub fn patterns(&self) -> impl Iterator<Item = Pattern<N>> + '_ {
+        self.rows.iter().map(|&mask| Pattern::from(mask))
+    }
+
+    /// The header and the grid, drawn with `theme`'s squares.
+    pub fn render(&self, theme: Theme) -> String {
+        let mut out = String::from("Wordle");
+        if let Some(puzzle) = self.puzzle {
+            out.push(' ');
+            out.push_str(&thousands(puzzle));
+        }
+        match self.guesses() {
+            Some(n) => out.push_str(&format!(" {}/{}", n, self.max_guesses)),
+            None => out.push_str(&format!(" X/{}", self.max_guesses)),
+        }
+        if self.hard_mode {
+            out.push('*');
+        }
+        out.push('\n');
+        for mask in &self.rows {
+            out.push('\n');
+            for &c in mask {
+                out.push_str(theme.square(c));
+            }
+        }
+        out
+    }
+}
+
+/// `1234` as `1,234`, the way the share header writes puzzle numbers.
+fn thousands(n: u32) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+/// Renders with the dark theme.
+impl<const N: usize> fmt::Display for Share<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Theme::Dark))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseShareError {
+    /// The first line looks like a header but isn't one.
+    Header(String),
+    /// A row, counting from 1, isn't a mask.
+    Row(usize, ParsePatternError),
+    /// The header's score doesn't agree with the rows.
+    Score { header: Option<usize>, rows: Option<usize> },
+    Empty,
+}
+
+impl fmt::Display for ParseShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let score = |n: &Option<usize>| n.map_or("X".to_string(), |n| n.to_string());
+        match self {
+            Self::Header(line) => write!(f, "'{}' is not a header like 'Wordle 512 4/6'", line),
+            Self::Row(row, e) => write!(f, "row {}: {}", row, e),
+            Self::Score { header, rows } => {
+                write!(f, "the header scores {} but the rows score {}", score(header), score(rows))
+            }
+            Self::Empty => write!(f, "no rows to parse"),
+        }
+    }
+}
+
+impl std::error::Error for ParseShareError {}
+
+/// Parses a pasted share: an optional `Wordle [number] n/m[*]` header (`X` for a lost game, with
+/// `,` or `.` grouping the number's digits), then one row of squares per line in any theme, or
+/// in the letter form `Pattern` parses. Blank lines are skipped.
+impl<const N: usize> FromStr for Share<N> {
+    type Err = ParseShareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty()).peekable();
+        let mut share = Share::from_masks(Vec::new());
+        let mut score = None;
+        if let Some(header) = lines.next_if(|line| line.to_ascii_lowercase().starts_with("wordle")) {
+            score = Some(parse_header(header, &mut share).ok_or_else(|| ParseShareError::Header(header.to_string()))?);
+        }
+        for (i, line) in lines.enumerate() {
+            let pattern: Pattern<N> = line.parse().map_err(|e| ParseShareError::Row(i + 1, e))?;
+            share.rows.push(pattern.mask());
+        }
+        if share.rows.is_empty() {
+            return Err(ParseShareError::Empty);
+        }
+        if let Some(header) = score {
+            if header != share.guesses() {
+                return Err(ParseShareError::Score { header, rows: share.guesses() });
+            }
+        }
+        Ok(share)
+    }
+}
+
+/// Fills in the header's fields of `share`, and returns its score.
+fn parse_header<const N: usize>(line: &str, share: &mut Share<N>) -> Option<Option<usize>> {
+    let mut words = line.split_whitespace().skip(1);
+    let mut word = words.next()?;
+    if !word.contains('/') {
+        let digits: String = word.chars().filter(|&c| c != ',' && c != '.').collect();
+        share.puzzle = Some(digits.parse().ok()?);
+        word = words.next()?;
+    }
+    if words.next().is_some() {
+        return None;
+    }
+    let (score, max) = word.split_once('/')?;
+    let max = match max.strip_suffix('*') {
+        Some(max) => {
+            share.hard_mode = true;
+            max
+        }
+        None => max,
+    };
+    share.max_guesses = max.parse().ok()?;
+    if score.eq_ignore_ascii_case("x") {
+        Some(None)
+    } else {
+        Some(Some(score.parse().ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseShareError, Share, Theme};
+    use crate::{Correctness, GameResult, Turn};
+    use std::time::Duration;
+
+    #[test]
+    fn parse() {
+        let share: Share = "Wordle 512 4/6*\n\n⬛🟨⬛⬛⬛\n⬛⬛🟩🟨⬛\n🟨🟩🟩⬛⬛\n🟩🟩🟩🟩🟩\n".parse().unwrap();
+        assert_eq!(share.puzzle, Some(512));
+        assert!(share.hard_mode);
+        assert_eq!(share.guesses(), Some(4));
+        assert_eq!(share.rows[0], mask![W M W W W]);
+
+        let light: Share = "Wordle 1,234 X/6\n⬜🟨⬜⬜⬜\n".parse().unwrap();
+        assert_eq!(light.puzzle, Some(1234));
+        assert_eq!(light.guesses(), None);
+        let contrast: Share = "🟧🟦⬛⬛⬛\n🟧🟧🟧🟧🟧".parse().unwrap();
+        assert_eq!(contrast.puzzle, None);
+        assert_eq!(contrast.rows, vec![mask![C M W W W], mask![C C C C C]]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("Wordle 512 3/6\n🟩🟩🟩🟩🟩".parse::<Share>(), Err(ParseShareError::Score { header: Some(3), rows: Some(1) }));
+        assert_eq!("Wordle five 1/6\n🟩🟩🟩🟩🟩".parse::<Share>(), Err(ParseShareError::Header("Wordle five 1/6".into())));
+        assert!(matches!("🟩🟩🟩🟩".parse::<Share>(), Err(ParseShareError::Row(1, _))));
+        assert_eq!("Wordle 512 X/6\n".parse::<Share>(), Err(ParseShareError::Empty));
+    }
+
+    #[test]
+    fn render() {
+        let turn = |word: &[u8; 5]| Turn { word: *word, mask: Correctness::compute(b"right", word), remaining: None, elapsed: Duration::ZERO };
+        let result = GameResult { answer: *b"right", turns: vec![turn(b"wrong"), turn(b"right")], won: true };
+        let share = Share::from_result(&result).with_puzzle(1234);
+        assert_eq!(share.to_string(), "Wordle 1,234 2/6\n\n⬛🟨⬛⬛🟨\n🟩🟩🟩🟩🟩");
+        assert_eq!(share.render(Theme::HighContrast).lines().last(), Some("🟧🟧🟧🟧🟧"));
+        assert_eq!(share.render(Theme::Light).parse::<Share>(), Ok(share.clone()));
+        assert_eq!(Share::from_masks(vec![mask![W W W W W]]).with_hard_mode(true).to_string(), "Wordle X/6*\n\n⬛⬛⬛⬛⬛");
+    }
+
+    #[test]
+    fn header_round_trip() {
+        let turn = |word: &[u8; 5]| Turn { word: *word, mask: Correctness::compute(b"right", word), remaining: None, elapsed: Duration::ZERO };
+        let won_in = |n: usize| {
+            let mut turns = vec![turn(b"wrong"); n - 1];
+            turns.push(turn(b"right"));
+            Share::from_result(&GameResult { answer: *b"right", turns, won: true }).with_puzzle(512)
+        };
+        for (n, header) in [(1, "Wordle 512 1/6"), (6, "Wordle 512 6/6"), (7, "Wordle 512 X/6")] {
+            let share = won_in(n);
+            assert_eq!(share.to_string().lines().next(), Some(header));
+            assert_eq!(share.to_string().parse::<Share>(), Ok(share.clone()));
+        }
+    }
+}
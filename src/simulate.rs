@@ -0,0 +1,75 @@
+use crate::{Error, GameResult, Guesser, Word, Wordle};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Plays every answer with a fresh guesser from `mk`, spread over `jobs` threads.
+///
+/// Games finish in whatever order the threads get to them, but `on_result` is always called
+/// in answer order, so the output of a run doesn't depend on `jobs`.
+pub fn simulate<G, const N: usize>(
+    wordle: &Wordle<N>,
+    answers: &[Word<N>],
+    mk: impl Fn() -> G + Sync,
+    jobs: usize,
+    mut on_result: impl FnMut(&Word<N>, Result<GameResult<N>, Error>),
+) where
+    G: Guesser<N> + Send,
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs.max(1) {
+            let tx = tx.clone();
+            let (next, mk) = (&next, &mk);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&answer) = answers.get(i) else { break };
+                if tx.send((i, wordle.try_play(answer, mk()))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        // hold on to games that finished early until everything before them is reported
+        let mut pending = BTreeMap::new();
+        let mut reported = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&reported) {
+                on_result(&answers[reported], result);
+                reported += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::simulate;
+    use crate::{algorithms::Matrix, Wordle};
+
+    #[test]
+    fn jobs_dont_change_results() {
+        let w = Wordle::new();
+        let answers = [*b"cigar", *b"rebut", *b"sissy", *b"humph", *b"awake", *b"blush"];
+        let run = |jobs| {
+            let mut played = Vec::new();
+            simulate(&w, &answers, Matrix::new, jobs, |answer, result| {
+                let result = result.unwrap();
+                assert_eq!(result.answer, *answer);
+                played.push((result.answer, result.turns.iter().map(|t| t.word).collect::<Vec<_>>()));
+            });
+            played
+        };
+        let serial = run(1);
+        assert_eq!(serial.iter().map(|(answer, _)| *answer).collect::<Vec<_>>(), answers);
+        assert_eq!(run(4), serial);
+    }
+}
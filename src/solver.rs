@@ -0,0 +1,237 @@
+use crate::{DecisionTree, Dictionary, Error, Pattern, Word};
+use std::collections::{BTreeMap, HashMap};
+
+/// Searches for the decision tree that solves a set of answers in the fewest total guesses.
+///
+/// The search is a depth-first branch and bound: guesses are tried in order of their lower
+/// bound, and a guess is dropped as soon as its subtrees can no longer beat the best one found
+/// so far. Subtrees are memoized on the exact set of answers left and the guesses left, since
+/// many guess orders lead to the same split. With every dictionary word as a candidate the
+/// result is optimal, but that is only practical for small answer lists; see `with_candidates`.
+pub struct Solver<'d, const N: usize = 5> {
+    dictionary: &'d Dictionary<N>,
+    max_depth: usize,
+    candidates: Option<usize>,
+    /// Fewest total guesses and the guess achieving it, per (answers left, guesses left), or
+    /// `None` when those answers can't all be solved in time.
+    memo: HashMap<(Box<[usize]>, usize), Option<Choice>>,
+}
+
+/// Total guesses, and the guess to make first.
+type Choice = (usize, usize);
+
+impl<'d, const N: usize> Solver<'d, N> {
+    /// A solver that may guess any word of `dictionary`, and has six guesses per game.
+    pub fn new(dictionary: &'d Dictionary<N>) -> Self {
+        Solver {
+            dictionary,
+            max_depth: 6,
+            candidates: None,
+            memo: HashMap::new(),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self.memo.clear();
+        self
+    }
+
+    /// Only tries the `k` guesses that split the answers left into the most patterns at each
+    /// node. This is much faster, but the tree is then only the best among those guesses.
+    pub fn with_candidates(mut self, k: usize) -> Self {
+        self.candidates = Some(k.max(1));
+        self.memo.clear();
+        self
+    }
+
+    /// The best tree for `answers`, or `None` if they can't all be solved within the depth.
+    pub fn solve(&mut self, answers: &[Word<N>]) -> Result<Option<DecisionTree<N>>, Error> {
+        let mut pool = answers
+            .iter()
+            .map(|answer| {
+                self.dictionary
+                    .index_of(answer)
+                    .ok_or_else(|| Error::InvalidGuess(String::from_utf8_lossy(answer).into_owned()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        pool.sort_unstable();
+        pool.dedup();
+        if pool.is_empty() || self.cost(&pool, self.max_depth).is_none() {
+            return Ok(None);
+        }
+        Ok(Some(self.build(&pool, self.max_depth)))
+    }
+
+    /// Fewest total guesses to solve every answer in `pool` with `depth` guesses each.
+    fn cost(&mut self, pool: &[usize], depth: usize) -> Option<usize> {
+        match (pool.len(), depth) {
+            (_, 0) => return None,
+            (1, _) => return Some(1),
+            (_, 1) => return None,
+            _ => {}
+        }
+        let key = (Box::from(pool), depth);
+        if let Some(&known) = self.memo.get(&key) {
+            return known.map(|(cost, _)| cost);
+        }
+        let best = self.search(pool, depth);
+        self.memo.insert(key, best);
+        best.map(|(cost, _)| cost)
+    }
+
+    fn search(&mut self, pool: &[usize], depth: usize) -> Option<Choice> {
+        // every answer takes at least one guess, and all but one of them at least two
+        let floor = 2 * pool.len() - 1;
+        let mut best: Option<Choice> = None;
+        for (bound, guess) in self.candidates(pool) {
+            if best.is_some_and(|(cost, _)| bound >= cost) {
+                // candidates are sorted by bound, so nothing after this can do better either
+                break;
+            }
+            let limit = best.map_or(usize::MAX, |(cost, _)| cost);
+            if let Some(cost) = self.try_guess(pool, depth, guess, bound, limit) {
+                best = Some((cost, guess));
+                if cost == floor {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    /// Total guesses for `pool` when starting with `guess`, unless that is `limit` or more.
+    fn try_guess(&mut self, pool: &[usize], depth: usize, guess: usize, bound: usize, limit: usize) -> Option<usize> {
+        let buckets = self.buckets(guess, pool);
+        let mut total = pool.len();
+        // what the buckets that haven't been solved yet will cost at the very least
+        let mut rest = bound - total;
+        for bucket in buckets.values() {
+            rest -= lower_bound(bucket.len());
+            total += self.cost(bucket, depth - 1)?;
+            if total + rest >= limit {
+                return None;
+            }
+        }
+        Some(total)
+    }
+
+    /// The guesses worth trying on `pool`, with a lower bound on their total, lowest first.
+    fn candidates(&self, pool: &[usize]) -> Vec<(usize, usize)> {
+        let mut in_pattern = vec![0usize; Pattern::<N>::COUNT];
+        let mut candidates: Vec<(usize, usize)> = (0..self.dictionary.len())
+            .filter_map(|guess| {
+                in_pattern.fill(0);
+                for &answer in pool {
+                    in_pattern[self.pattern(guess, answer).index()] += 1;
+                }
+                let solved = in_pattern[Pattern::<N>::ALL_CORRECT.index()];
+                // all green is pattern 0, and costs nothing more
+                let bound = pool.len()
+                    + in_pattern[1..].iter().filter(|&&n| n != 0).map(|&n| lower_bound(n)).sum::<usize>();
+                // a guess that leaves every answer in one bucket learns nothing
+                let splits = solved != 0 || in_pattern.iter().all(|&n| n != pool.len());
+                splits.then_some((bound, guess))
+            })
+            .collect();
+        candidates.sort_unstable();
+        if let Some(k) = self.candidates {
+            candidates.truncate(k);
+        }
+        candidates
+    }
+
+    /// The answers of `pool` by the pattern `guess` gets against them, leaving out the answer
+    /// `guess` solves.
+    fn buckets(&self, guess: usize, pool: &[usize]) -> BTreeMap<Pattern<N>, Vec<usize>> {
+        let mut buckets: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for &answer in pool {
+            let pattern = self.pattern(guess, answer);
+            if !pattern.is_solved() {
+                buckets.entry(pattern).or_default().push(answer);
+            }
+        }
+        buckets
+    }
+
+    /// Computed on the spot rather than with `Dictionary::row`: every dictionary word gets tried
+    /// as a guess, and a full row for each of them is far more than the few answers scored here.
+    fn pattern(&self, guess: usize, answer: usize) -> Pattern<N> {
+        let words = self.dictionary.words();
+        Pattern::compute(&words[answer].0, &words[guess].0)
+    }
+
+    /// Turns the memoized choices for `pool` back into a tree.
+    fn build(&self, pool: &[usize], depth: usize) -> DecisionTree<N> {
+        let words = self.dictionary.words();
+        if let [answer] = pool {
+            return DecisionTree::leaf(words[*answer].0);
+        }
+        let (_, guess) = self.memo[&(Box::from(pool), depth)].expect("pool was solved before building");
+        let children = self
+            .buckets(guess, pool)
+            .into_iter()
+            .map(|(pattern, bucket)| (pattern, self.build(&bucket, depth - 1)))
+            .collect();
+        DecisionTree {
+            guess: words[guess].0,
+            is_answer: pool.contains(&guess),
+            children,
+        }
+    }
+}
+
+/// Fewest total guesses `n` answers could possibly take.
+fn lower_bound(n: usize) -> usize {
+    2 * n - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Solver;
+    use crate::{Dictionary, Wordle};
+
+    /// Every guess at every node, no pruning, no memo.
+    fn brute(d: &Dictionary, pool: &[usize], depth: usize) -> Option<usize> {
+        match (pool.len(), depth) {
+            (_, 0) => return None,
+            (1, _) => return Some(1),
+            _ => {}
+        }
+        (0..d.len())
+            .filter_map(|guess| {
+                let mut buckets = std::collections::BTreeMap::<_, Vec<_>>::new();
+                for &answer in pool.iter().filter(|&&answer| answer != guess) {
+                    buckets.entry(d.pattern(guess, answer)).or_default().push(answer);
+                }
+                if buckets.values().any(|bucket| bucket.len() == pool.len()) {
+                    return None;
+                }
+                buckets.values().try_fold(pool.len(), |total, bucket| Some(total + brute(d, bucket, depth - 1)?))
+            })
+            .min()
+    }
+
+    #[test]
+    fn optimal() {
+        let d = Dictionary::<5>::parse("batch\nbatty\nbaste\ncatch\nhatch\nlatch\nmatch\npatch\nwatch\nchimp\n").unwrap();
+        let answers: Vec<_> = d.words().iter().take(9).map(|(word, _)| *word).collect();
+        let tree = Solver::new(&d).solve(&answers).unwrap().unwrap();
+        assert_eq!(tree.answers(), 9);
+        assert_eq!(Some(tree.total_guesses()), brute(&d, &(0..9).collect::<Vec<_>>(), 6));
+
+        let w = Wordle::with_dictionary(&d);
+        for answer in &answers {
+            assert!(w.play(*answer, crate::TreeGuesser::new(&tree)).guesses().unwrap() <= tree.depth());
+        }
+    }
+
+    #[test]
+    fn too_deep() {
+        let d = Dictionary::<5>::parse("catch\nhatch\nlatch\nmatch\n").unwrap();
+        let answers: Vec<_> = d.words().iter().map(|(word, _)| *word).collect();
+        assert_eq!(Solver::new(&d).with_max_depth(2).solve(&answers), Ok(None));
+        let tree = Solver::new(&d).with_max_depth(4).solve(&answers).unwrap().unwrap();
+        assert_eq!(tree.total_guesses(), 1 + 2 + 3 + 4);
+    }
+}
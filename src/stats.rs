@@ -0,0 +1,199 @@
+use crate::{game::millis, GameResult, Word};
+use serde_json::{json, Value};
+use std::{fmt, time::Duration};
+
+/// Aggregate numbers over a batch of games, e.g. a whole run over `answers.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary<const N: usize = 5> {
+    pub games: usize,
+    pub wins: usize,
+    /// Guesses per won game; `None` when nothing was won.
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    pub p95: Option<usize>,
+    /// Won games by number of guesses: 1, 2, 3, 4, 5 and 6+.
+    pub histogram: [usize; 6],
+    /// Answers that weren't guessed in time, in the order they were played.
+    pub failures: Vec<Word<N>>,
+    /// Time the guessers spent thinking, over all games.
+    pub total_time: Duration,
+    /// Time the whole run took, if the harness measured it.
+    pub wall_time: Option<Duration>,
+}
+
+impl<const N: usize> Summary<N> {
+    pub fn from_results<'a>(results: impl IntoIterator<Item = &'a GameResult<N>>) -> Self {
+        let mut games = 0;
+        let mut guesses = Vec::new();
+        let mut histogram = [0; 6];
+        let mut failures = Vec::new();
+        let mut total_time = Duration::ZERO;
+        for result in results {
+            games += 1;
+            total_time += result.elapsed();
+            match result.guesses() {
+                Some(n) => {
+                    guesses.push(n);
+                    histogram[n.clamp(1, 6) - 1] += 1;
+                }
+                None => failures.push(result.answer),
+            }
+        }
+        guesses.sort_unstable();
+
+        let wins = guesses.len();
+        let mean = (wins != 0).then(|| guesses.iter().sum::<usize>() as f64 / wins as f64);
+        let median = (wins != 0).then(|| {
+            if wins % 2 == 0 {
+                (guesses[wins / 2 - 1] + guesses[wins / 2]) as f64 / 2.0
+            } else {
+                guesses[wins / 2] as f64
+            }
+        });
+        // nearest-rank percentile
+        let p95 = (wins != 0).then(|| guesses[(wins * 95).div_ceil(100) - 1]);
+        Summary {
+            games,
+            wins,
+            mean,
+            median,
+            p95,
+            histogram,
+            failures,
+            total_time,
+            wall_time: None,
+        }
+    }
+
+    pub fn with_wall_time(mut self, wall_time: Duration) -> Self {
+        self.wall_time = Some(wall_time);
+        self
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.games as f64
+    }
+
+    /// Mean guesser time per game.
+    pub fn per_game(&self) -> Duration {
+        if self.games == 0 {
+            return Duration::ZERO;
+        }
+        self.total_time / self.games as u32
+    }
+
+    /// The summary as a single JSON object, for `--format json`.
+    pub fn to_json(&self) -> Value {
+        let failures: Vec<_> = self.failures.iter().map(|w| String::from_utf8_lossy(w)).collect();
+        json!({
+            "games": self.games,
+            "wins": self.wins,
+            "win_rate": self.win_rate(),
+            "mean": self.mean,
+            "median": self.median,
+            "p95": self.p95,
+            "histogram": self.histogram,
+            "failures": failures,
+            "total_ms": millis(self.total_time),
+            "per_game_ms": millis(self.per_game()),
+            "wall_ms": self.wall_time.map(millis),
+        })
+    }
+
+    pub const CSV_HEADER: &'static str = "games,wins,win_rate,mean,median,p95,total_ms,per_game_ms,wall_ms";
+
+    /// One row under `CSV_HEADER`; the histogram and failures are left to the per-game rows.
+    pub fn to_csv(&self) -> String {
+        fn opt<T: ToString>(v: Option<T>) -> String {
+            v.map_or(String::new(), |v| v.to_string())
+        }
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.games,
+            self.wins,
+            self.win_rate(),
+            opt(self.mean),
+            opt(self.median),
+            opt(self.p95),
+            millis(self.total_time),
+            millis(self.per_game()),
+            opt(self.wall_time.map(millis)),
+        )
+    }
+}
+
+impl<const N: usize> fmt::Display for Summary<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "games played: {}", self.games)?;
+        writeln!(f, "win rate:     {:.2}% ({} won)", 100.0 * self.win_rate(), self.wins)?;
+        if let (Some(mean), Some(median), Some(p95)) = (self.mean, self.median, self.p95) {
+            writeln!(f, "guesses:      mean {:.3}, median {}, p95 {}", mean, median, p95)?;
+        }
+        let most = self.histogram.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in self.histogram.iter().enumerate() {
+            let label = if i == 5 { "6+".to_owned() } else { (i + 1).to_string() };
+            writeln!(f, "  {:>2}: {:>5} {}", label, count, "#".repeat(count * 40 / most))?;
+        }
+        if !self.failures.is_empty() {
+            let failures: Vec<_> = self.failures.iter().map(|w| String::from_utf8_lossy(w)).collect();
+            writeln!(f, "failed:       {}", failures.join(", "))?;
+        }
+        write!(f, "time:         {:?} total, {:?} per game", self.total_time, self.per_game())?;
+        if let Some(wall_time) = self.wall_time {
+            write!(f, ", {:?} wall", wall_time)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Summary;
+    use crate::{Correctness, GameResult, Turn};
+    use std::time::Duration;
+
+    fn game(answer: &[u8; 5], guesses: usize, won: bool) -> GameResult {
+        let turn = Turn {
+            word: *answer,
+            mask: [Correctness::Correct; 5],
+            remaining: None,
+            elapsed: Duration::from_millis(10),
+        };
+        GameResult { answer: *answer, turns: vec![turn; guesses], won }
+    }
+
+    #[test]
+    fn summary() {
+        let results = [
+            game(b"cigar", 3, true),
+            game(b"rebut", 4, true),
+            game(b"sissy", 7, false),
+            game(b"humph", 7, true),
+            game(b"awake", 3, true),
+        ];
+        let s = Summary::from_results(&results);
+        assert_eq!(s.games, 5);
+        assert_eq!(s.wins, 4);
+        assert_eq!(s.win_rate(), 0.8);
+        assert_eq!(s.mean, Some(4.25));
+        assert_eq!(s.median, Some(3.5));
+        assert_eq!(s.p95, Some(7));
+        assert_eq!(s.histogram, [0, 0, 2, 1, 0, 1]);
+        assert_eq!(s.failures, vec![*b"sissy"]);
+        assert_eq!(s.total_time, Duration::from_millis(240));
+        assert_eq!(s.per_game(), Duration::from_millis(48));
+        assert_eq!(s.to_json()["failures"][0], "sissy");
+        assert_eq!(s.to_json()["histogram"][2], 2);
+        assert_eq!(s.to_csv(), "5,4,0.8,4.25,3.5,7,240,48,");
+    }
+
+    #[test]
+    fn empty() {
+        let s = Summary::<5>::from_results(&[]);
+        assert_eq!(s.mean, None);
+        assert_eq!(s.win_rate(), 0.0);
+    }
+}
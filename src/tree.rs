@@ -0,0 +1,369 @@
+use crate::{Correctness, Dictionary, Error, Guess, Guesser, Pattern, Word};
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// A complete strategy: what to guess first, and for every pattern that can come back, the
+/// strategy for the answers that are left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTree<const N: usize = 5> {
+    pub guess: Word<N>,
+    /// Whether `guess` is one of the answers, i.e. can come back all green.
+    pub is_answer: bool,
+    /// What to do after every other pattern.
+    pub children: BTreeMap<Pattern<N>, DecisionTree<N>>,
+}
+
+impl<const N: usize> DecisionTree<N> {
+    /// A node that just guesses the one answer left.
+    pub fn leaf(answer: Word<N>) -> Self {
+        DecisionTree { guess: answer, is_answer: true, children: BTreeMap::new() }
+    }
+
+    /// The subtree to follow once `guess` got `pattern`.
+    pub fn next(&self, pattern: Pattern<N>) -> Option<&Self> {
+        self.children.get(&pattern)
+    }
+
+    /// How many answers the tree solves.
+    pub fn answers(&self) -> usize {
+        self.is_answer as usize + self.children.values().map(Self::answers).sum::<usize>()
+    }
+
+    /// Guesses needed to solve every answer once, so `total_guesses() / answers()` is the mean.
+    pub fn total_guesses(&self) -> usize {
+        fn walk<const N: usize>(node: &DecisionTree<N>, depth: usize) -> usize {
+            let here = if node.is_answer { depth } else { 0 };
+            here + node.children.values().map(|child| walk(child, depth + 1)).sum::<usize>()
+        }
+        walk(self, 1)
+    }
+
+    /// The most guesses any answer takes.
+    pub fn depth(&self) -> usize {
+        1 + self.children.values().map(Self::depth).max().unwrap_or(0)
+    }
+
+    /// The tree as nested `{"guess", "answer", "next"}` objects, with `next` keyed by
+    /// patterns like `..y.g`.
+    pub fn to_json(&self) -> Value {
+        let mut node = json!({
+            "guess": String::from_utf8_lossy(&self.guess),
+            "answer": self.is_answer,
+        });
+        if !self.children.is_empty() {
+            let next: Map<_, _> =
+                self.children.iter().map(|(pattern, child)| (format!("{:#}", pattern), child.to_json())).collect();
+            node["next"] = Value::Object(next);
+        }
+        node
+    }
+
+    /// Reads back what `to_json` wrote.
+    pub fn from_json(value: &Value) -> Result<Self, Error> {
+        let malformed = |why: String| Error::MalformedTree(why);
+        let guess = value["guess"].as_str().ok_or_else(|| malformed("node without a \"guess\"".to_owned()))?;
+        let is_answer = value["answer"].as_bool().unwrap_or(false);
+        let guess: Word<N> = guess.as_bytes().try_into().map_err(|_| Error::WrongWordLength {
+            word: guess.to_owned(),
+            expected: N,
+        })?;
+        let mut children = BTreeMap::new();
+        match &value["next"] {
+            Value::Null => {}
+            Value::Object(next) => {
+                for (pattern, child) in next {
+                    let pattern = pattern
+                        .parse()
+                        .map_err(|e| malformed(format!("bad pattern '{}': {}", pattern, e)))?;
+                    children.insert(pattern, Self::from_json(child)?);
+                }
+            }
+            _ => return Err(malformed("\"next\" is not an object".to_owned())),
+        }
+        Ok(DecisionTree { guess, is_answer, children })
+    }
+
+    /// The tree as one line per answer, spelling out the whole game that solves it, like
+    /// `salet BBBBB1 courd BBBGB2 whump GGGGG3`. Squares are `B` gray, `Y` yellow and `G` green,
+    /// each followed by the guess number.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        self.write_text("", 1, &mut out);
+        out
+    }
+
+    fn write_text(&self, prefix: &str, turn: usize, out: &mut String) {
+        let word = String::from_utf8_lossy(&self.guess);
+        if self.is_answer {
+            out.push_str(&format!("{}{} {}{}\n", prefix, word, squares(Pattern::<N>::ALL_CORRECT), turn));
+        }
+        for (pattern, child) in &self.children {
+            child.write_text(&format!("{}{} {}{} ", prefix, word, squares(*pattern), turn), turn + 1, out);
+        }
+    }
+
+    /// Reads back what `to_text` wrote. Lines may come in any order, as long as they agree on
+    /// what to guess after each pattern.
+    pub fn parse_text(s: &str) -> Result<Self, Error> {
+        let mut root: Option<Self> = None;
+        for (i, line) in s.lines().enumerate() {
+            let malformed = |why: String| Error::MalformedTree(format!("line {}: {}", i + 1, why));
+            let tokens: Vec<_> = line.split_whitespace().collect();
+            if tokens.is_empty() {
+                continue;
+            }
+            if tokens.len() % 2 != 0 {
+                return Err(malformed("expected word and pattern pairs".to_owned()));
+            }
+            let mut steps = Vec::new();
+            for (turn, pair) in tokens.chunks(2).enumerate() {
+                let word: Word<N> = pair[0].as_bytes().try_into().map_err(|_| Error::WrongWordLength {
+                    word: pair[0].to_owned(),
+                    expected: N,
+                })?;
+                let digits = pair[1].find(|c: char| c.is_ascii_digit()).unwrap_or(pair[1].len());
+                let (pattern, number) = pair[1].split_at(digits);
+                let pattern: Pattern<N> =
+                    pattern.parse().map_err(|e| malformed(format!("bad pattern '{}': {}", pair[1], e)))?;
+                if number.parse() != Ok(turn + 1) {
+                    return Err(malformed(format!("'{}' should be numbered {}", pair[1], turn + 1)));
+                }
+                steps.push((word, pattern));
+            }
+            let (&(_, last), path) = steps.split_last().expect("line has tokens");
+            if !last.is_solved() || path.iter().any(|(_, pattern)| pattern.is_solved()) {
+                return Err(malformed("only the last guess can be all green".to_owned()));
+            }
+
+            let mut node = root.get_or_insert_with(|| DecisionTree {
+                guess: steps[0].0,
+                is_answer: false,
+                children: BTreeMap::new(),
+            });
+            for (turn, &(word, pattern)) in steps.iter().enumerate() {
+                if node.guess != word {
+                    return Err(malformed(format!(
+                        "guess {} is '{}', but another line has '{}' there",
+                        turn + 1,
+                        String::from_utf8_lossy(&word),
+                        String::from_utf8_lossy(&node.guess)
+                    )));
+                }
+                if pattern.is_solved() {
+                    break;
+                }
+                let next = steps[turn + 1].0;
+                node = node.children.entry(pattern).or_insert_with(|| DecisionTree {
+                    guess: next,
+                    is_answer: false,
+                    children: BTreeMap::new(),
+                });
+            }
+            node.is_answer = true;
+        }
+        root.ok_or_else(|| Error::MalformedTree("no lines".to_owned()))
+    }
+
+    /// Parses either format, telling them apart by whether it looks like JSON.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        if s.trim_start().starts_with('{') {
+            let value = serde_json::from_str(s).map_err(|e| Error::MalformedTree(e.to_string()))?;
+            Self::from_json(&value)
+        } else {
+            Self::parse_text(s)
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Checks that every guess is in `dictionary`, that every answer in the tree really gets
+    /// the patterns on its path, and that every one of `answers` ends up solved.
+    pub fn validate(&self, dictionary: &Dictionary<N>, answers: &[Word<N>]) -> Result<(), Error> {
+        fn walk<const N: usize>(
+            node: &DecisionTree<N>,
+            path: &mut Vec<(Word<N>, Pattern<N>)>,
+            dictionary: &Dictionary<N>,
+        ) -> Result<(), Error> {
+            if !dictionary.contains(&node.guess) {
+                return Err(Error::InvalidGuess(String::from_utf8_lossy(&node.guess).into_owned()));
+            }
+            if node.is_answer {
+                for &(guess, pattern) in path.iter() {
+                    if Pattern::compute(&node.guess, &guess) != pattern {
+                        return Err(Error::MalformedTree(format!(
+                            "'{}' is filed under {} for '{}', but would get {}",
+                            String::from_utf8_lossy(&node.guess),
+                            squares(pattern),
+                            String::from_utf8_lossy(&guess),
+                            squares(Pattern::compute(&node.guess, &guess))
+                        )));
+                    }
+                }
+            }
+            for (&pattern, child) in &node.children {
+                path.push((node.guess, pattern));
+                walk(child, path, dictionary)?;
+                path.pop();
+            }
+            Ok(())
+        }
+        walk(self, &mut Vec::new(), dictionary)?;
+
+        for answer in answers {
+            let mut node = self;
+            loop {
+                let pattern = Pattern::compute(answer, &node.guess);
+                if pattern.is_solved() && node.is_answer {
+                    break;
+                }
+                node = node.next(pattern).ok_or_else(|| {
+                    Error::MalformedTree(format!("'{}' is never solved", String::from_utf8_lossy(answer)))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `B`/`Y`/`G` form of the text format.
+fn squares<const N: usize>(pattern: Pattern<N>) -> String {
+    pattern
+        .mask()
+        .iter()
+        .map(|c| match c {
+            Correctness::Correct => 'G',
+            Correctness::Misplaced => 'Y',
+            Correctness::Wrong => 'B',
+        })
+        .collect()
+}
+
+/// Plays a precomputed strategy by following the patterns of the history down the tree.
+pub struct TreeGuesser<'t, const N: usize = 5> {
+    tree: &'t DecisionTree<N>,
+    /// Answers under the node of the last guess.
+    remaining: Option<usize>,
+}
+
+impl<'t, const N: usize> TreeGuesser<'t, N> {
+    pub fn new(tree: &'t DecisionTree<N>) -> Self {
+        TreeGuesser { tree, remaining: None }
+    }
+}
+
+impl<const N: usize> Guesser<N> for TreeGuesser<'_, N> {
+    fn guess(&mut self, history: &[Guess<N>]) -> Word<N> {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails if a guess in `history` isn't the one the tree would have made, or got a pattern
+    /// the tree has no plan for.
+    fn try_guess(&mut self, history: &[Guess<N>]) -> Result<Word<N>, Error> {
+        let mut node = self.tree;
+        for (turn, g) in history.iter().enumerate() {
+            if *g.word != node.guess {
+                return Err(Error::LeftTree(format!(
+                    "guess {} was '{}', but the tree plays '{}'",
+                    turn + 1,
+                    String::from_utf8_lossy(&g.word[..]),
+                    String::from_utf8_lossy(&node.guess)
+                )));
+            }
+            node = node.next(g.pattern()).ok_or_else(|| {
+                Error::LeftTree(format!(
+                    "nothing follows '{}' getting {} at guess {}",
+                    String::from_utf8_lossy(&g.word[..]),
+                    squares(g.pattern()),
+                    turn + 1
+                ))
+            })?;
+        }
+        self.remaining = Some(node.answers());
+        Ok(node.guess)
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecisionTree, TreeGuesser};
+    use crate::{Dictionary, Error, Guess, Guesser, Pattern, Wordle};
+    use std::{borrow::Cow, collections::BTreeMap};
+
+    fn tree() -> DecisionTree {
+        let mut children = BTreeMap::new();
+        children.insert(Pattern::compute(b"right", b"wrong"), DecisionTree::leaf(*b"right"));
+        children.insert(Pattern::compute(b"might", b"wrong"), DecisionTree::leaf(*b"might"));
+        DecisionTree { guess: *b"wrong", is_answer: true, children }
+    }
+
+    #[test]
+    fn stats() {
+        let tree = tree();
+        assert_eq!(tree.answers(), 3);
+        assert_eq!(tree.total_guesses(), 5);
+        assert_eq!(tree.depth(), 2);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let tree = tree();
+        let json = tree.to_json();
+        assert_eq!(json["next"][".y..y"]["guess"], "right");
+        assert_eq!(DecisionTree::from_json(&json), Ok(tree));
+    }
+
+    #[test]
+    fn text_roundtrip() {
+        let tree = tree();
+        let text = tree.to_text();
+        assert_eq!(text, "wrong GGGGG1\nwrong BYBBY1 right GGGGG2\nwrong BBBBY1 might GGGGG2\n");
+        assert_eq!(DecisionTree::parse_text(&text), Ok(tree.clone()));
+        // any order will do
+        let shuffled: Vec<_> = text.lines().rev().collect();
+        assert_eq!(DecisionTree::parse(&shuffled.join("\n")), Ok(tree));
+    }
+
+    #[test]
+    fn bad_text() {
+        let parse = DecisionTree::<5>::parse_text;
+        assert!(matches!(parse("wrong BYBBY1 right GGGGG3"), Err(Error::MalformedTree(_))));
+        assert!(matches!(parse("wrong BYBBY1 right"), Err(Error::MalformedTree(_))));
+        assert!(matches!(parse("wrong BYBBY1"), Err(Error::MalformedTree(_))));
+        assert!(matches!(
+            parse("wrong BYBBY1 right GGGGG2\nwrong BYBBY1 night GGGGG2"),
+            Err(Error::MalformedTree(_))
+        ));
+    }
+
+    #[test]
+    fn validate() {
+        let d = Dictionary::<5>::parse("wrong\nright\nmight\nnight\n").unwrap();
+        let tree = tree();
+        assert_eq!(tree.validate(&d, &[*b"wrong", *b"right", *b"might"]), Ok(()));
+        assert!(matches!(tree.validate(&d, &[*b"night"]), Err(Error::MalformedTree(_))));
+
+        let mut swapped = tree.clone();
+        swapped.children.values_mut().for_each(|child| child.guess = *b"night");
+        assert!(matches!(swapped.validate(&d, &[]), Err(Error::MalformedTree(_))));
+    }
+
+    #[test]
+    fn guesser() {
+        let d = Dictionary::<5>::parse("wrong\nright\nmight\nnight\n").unwrap();
+        let tree = tree();
+        let result = Wordle::with_dictionary(&d).play(*b"might", TreeGuesser::new(&tree));
+        assert_eq!(result.guesses(), Some(2));
+        assert_eq!(result.turns[0].remaining, Some(3));
+
+        let off = [Guess { word: Cow::Borrowed(b"wrong"), mask: Pattern::compute(b"night", b"wrong").mask() }];
+        assert!(matches!(TreeGuesser::new(&tree).try_guess(&off), Err(Error::LeftTree(_))));
+        let other = [Guess { word: Cow::Borrowed(b"night"), mask: Pattern::compute(b"night", b"night").mask() }];
+        assert!(matches!(TreeGuesser::new(&tree).try_guess(&other), Err(Error::LeftTree(_))));
+    }
+}